@@ -1,9 +1,9 @@
 use crate::{
     sys,
-    utils::{charbuf_to_str, write_charbuf},
+    utils::{charbuf_to_str, seconds_between, unix_now, write_charbuf},
     ActivityKind, ClientID, UnixTimestamp,
 };
-use std::convert::TryInto;
+use std::{convert::TryInto, time::Duration};
 
 /// Activity (also known as Rich Presence)
 ///
@@ -84,6 +84,38 @@ impl Activity {
         self.0.timestamps.end
     }
 
+    /// How long ago the current activity started, `None` if the start time is not set
+    ///
+    /// Saturates to zero if the start time is in the future.
+    ///
+    /// ```rust
+    /// # use discord_game_sdk::*;
+    /// # fn example(activity: Activity) {
+    /// if let Some(elapsed) = activity.elapsed() {
+    ///     let seconds = elapsed.as_secs();
+    ///     println!("{}:{:02} elapsed", seconds / 60, seconds % 60);
+    /// }
+    /// # }
+    /// ```
+    pub fn elapsed(&self) -> Option<Duration> {
+        if self.start_time() == 0 {
+            return None;
+        }
+
+        Some(seconds_between(self.start_time(), unix_now()))
+    }
+
+    /// How long until the current activity ends, `None` if the end time is not set
+    ///
+    /// Saturates to zero if the end time has passed.
+    pub fn remaining(&self) -> Option<Duration> {
+        if self.end_time() == 0 {
+            return None;
+        }
+
+        Some(seconds_between(unix_now(), self.end_time()))
+    }
+
     /// The key of an asset to display
     pub fn large_image_key(&self) -> &str {
         charbuf_to_str(&self.0.assets.large_image)
@@ -252,6 +284,28 @@ impl Activity {
         write_charbuf(&mut self.0.secrets.spectate, value);
         self
     }
+
+    /// Clears the match, join and spectate secrets, keeping all other fields
+    ///
+    /// Useful once a match has ended, so that players can no longer join or spectate it.
+    ///
+    /// ```rust
+    /// # use discord_game_sdk::*;
+    /// # fn example(discord: Discord<'_, ()>, mut activity: Activity) -> Result<()> {
+    /// discord.update_activity(
+    ///     activity.without_secrets().with_state("Match Ended"),
+    ///     |discord, result| {
+    ///         if let Err(error) = result {
+    ///             eprintln!("failed to update activity: {}", error);
+    ///         }
+    ///     },
+    /// );
+    /// # Ok(()) }
+    /// ```
+    pub fn without_secrets(&mut self) -> &mut Self {
+        self.0.secrets = sys::DiscordActivitySecrets::default();
+        self
+    }
 }
 
 impl std::fmt::Debug for Activity {
@@ -306,3 +360,25 @@ impl std::fmt::Display for Activity {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn elapsed_and_remaining() {
+        let now = unix_now();
+        let mut activity = Activity::empty();
+
+        assert_eq!(activity.elapsed(), None);
+        assert_eq!(activity.remaining(), None);
+
+        activity.with_start_time(now - 60).with_end_time(now + 3600);
+        assert!(activity.elapsed().unwrap() >= Duration::from_secs(60));
+        assert!(activity.remaining().unwrap() <= Duration::from_secs(3600));
+
+        activity.with_start_time(now + 60).with_end_time(now - 60);
+        assert_eq!(activity.elapsed(), Some(Duration::from_secs(0)));
+        assert_eq!(activity.remaining(), Some(Duration::from_secs(0)));
+    }
+}
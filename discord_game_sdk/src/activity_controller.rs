@@ -0,0 +1,150 @@
+use crate::{Activity, Discord, Result};
+
+/// Keeps track of the last [`Activity`] set through it, to pause and resume Rich Presence,
+/// e.g. when the game loses focus
+///
+/// [`Discord::update_activity`] and [`Discord::clear_activity`] remain available for direct
+/// control, but are not tracked by the controller.
+///
+/// [`Activity`]: struct.Activity.html
+/// [`Discord::update_activity`]: struct.Discord.html#method.update_activity
+/// [`Discord::clear_activity`]: struct.Discord.html#method.clear_activity
+///
+/// ```rust
+/// # use discord_game_sdk::*;
+/// # fn example(discord: Discord<'_, ()>, focused: bool) -> Result<()> {
+/// let mut controller = ActivityController::new();
+///
+/// controller.update(
+///     &discord,
+///     Activity::empty().with_state("In Match"),
+///     |_, _| {},
+/// );
+///
+/// if focused {
+///     controller.resume(&discord, |_, _| {});
+/// } else {
+///     controller.pause_with_state(&discord, "Paused", |_, _| {});
+/// }
+/// # Ok(()) }
+/// ```
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct ActivityController {
+    activity: Option<Activity>,
+    paused: bool,
+    // Set by `pause_with_state`, the state shown instead of the activity's own while paused
+    paused_state: Option<String>,
+}
+
+impl ActivityController {
+    /// Create a new controller, with no activity and not paused
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The last activity set through [`update`](#method.update),
+    /// `None` if it was never set or has been cleared
+    pub fn activity(&self) -> Option<&Activity> {
+        self.activity.as_ref()
+    }
+
+    /// Whether the controller is currently paused
+    pub fn is_paused(&self) -> bool {
+        self.paused
+    }
+
+    /// Sets the user's current presence and remembers it.
+    ///
+    /// While paused, the activity is remembered and will be set on [`resume`](#method.resume).
+    /// If paused with [`pause_with_state`](#method.pause_with_state), the new activity is set with
+    /// the paused state, otherwise `callback` immediately receives `Ok(())`.
+    pub fn update<'d, E>(
+        &mut self,
+        discord: &Discord<'d, E>,
+        activity: &Activity,
+        callback: impl 'd + FnOnce(&Discord<'d, E>, Result<()>),
+    ) {
+        self.activity = Some(activity.clone());
+
+        if self.paused {
+            return match self.paused_state {
+                Some(ref state) => {
+                    discord.update_activity(activity.clone().with_state(state), callback)
+                }
+                None => callback(discord, Ok(())),
+            };
+        }
+
+        discord.update_activity(activity, callback)
+    }
+
+    /// Clears the user's current presence and forgets it.
+    ///
+    /// While paused, the presence is only cleared if it was paused with
+    /// [`pause_with_state`](#method.pause_with_state), otherwise `callback` immediately
+    /// receives `Ok(())`.
+    pub fn clear<'d, E>(
+        &mut self,
+        discord: &Discord<'d, E>,
+        callback: impl 'd + FnOnce(&Discord<'d, E>, Result<()>),
+    ) {
+        self.activity = None;
+
+        if self.paused && self.paused_state.is_none() {
+            return callback(discord, Ok(()));
+        }
+
+        discord.clear_activity(callback)
+    }
+
+    /// Clears the user's current presence, remembering the activity until
+    /// [`resume`](#method.resume) is called.
+    pub fn pause<'d, E>(
+        &mut self,
+        discord: &Discord<'d, E>,
+        callback: impl 'd + FnOnce(&Discord<'d, E>, Result<()>),
+    ) {
+        self.paused = true;
+        self.paused_state = None;
+
+        discord.clear_activity(callback)
+    }
+
+    /// Replaces the state of the remembered activity (e.g. with `"Paused"`) until
+    /// [`resume`](#method.resume) is called.
+    ///
+    /// If no activity is remembered, `callback` immediately receives `Ok(())`.
+    pub fn pause_with_state<'d, E>(
+        &mut self,
+        discord: &Discord<'d, E>,
+        state: &str,
+        callback: impl 'd + FnOnce(&Discord<'d, E>, Result<()>),
+    ) {
+        self.paused = true;
+        self.paused_state = Some(state.to_string());
+
+        match self.activity {
+            Some(ref activity) => {
+                discord.update_activity(activity.clone().with_state(state), callback)
+            }
+            None => callback(discord, Ok(())),
+        }
+    }
+
+    /// Sets the remembered activity again.
+    ///
+    /// If no activity is remembered, `callback` immediately receives `Ok(())`.
+    pub fn resume<'d, E>(
+        &mut self,
+        discord: &Discord<'d, E>,
+        callback: impl 'd + FnOnce(&Discord<'d, E>, Result<()>),
+    ) {
+        self.paused = false;
+        self.paused_state = None;
+
+        match self.activity {
+            Some(ref activity) => discord.update_activity(activity, callback),
+            None => callback(discord, Ok(())),
+        }
+    }
+}
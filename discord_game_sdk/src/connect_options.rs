@@ -0,0 +1,37 @@
+/// Lobby Connection Options
+///
+/// Used by [`Discord::connect_lobby_with_options`](struct.Discord.html#method.connect_lobby_with_options),
+/// both the network and voice are connected by default.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct ConnectOptions {
+    pub(crate) network: bool,
+    pub(crate) voice: bool,
+}
+
+impl Default for ConnectOptions {
+    fn default() -> Self {
+        Self {
+            network: true,
+            voice: true,
+        }
+    }
+}
+
+impl ConnectOptions {
+    /// Creates options that connect both the network and voice
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Whether to connect to the lobby's network layer
+    pub fn network(&mut self, network: bool) -> &mut Self {
+        self.network = network;
+        self
+    }
+
+    /// Whether to connect to the lobby's voice channel
+    pub fn voice(&mut self, voice: bool) -> &mut Self {
+        self.voice = voice;
+        self
+    }
+}
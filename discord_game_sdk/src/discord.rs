@@ -1,5 +1,10 @@
-use crate::{sys, ClientID};
-use std::{cell::UnsafeCell, marker::PhantomData, mem::ManuallyDrop};
+use crate::{sys, ClientID, Error, LobbyID, Result, UserID};
+use std::{
+    cell::{Cell, RefCell, UnsafeCell},
+    collections::{HashMap, HashSet},
+    marker::PhantomData,
+    mem::ManuallyDrop,
+};
 
 /// Main interface with SDK
 ///
@@ -13,6 +18,9 @@ use std::{cell::UnsafeCell, marker::PhantomData, mem::ManuallyDrop};
 ///
 /// All `callback`s will be called with `Err(TransactionAborted)` when the instance is dropped
 ///
+/// A panic in a `callback` is caught and logged, it does not unwind into the SDK.
+/// A panic in an [`EventHandler`](trait.EventHandler.html) method aborts the process.
+///
 /// ```rust,compile_fail
 /// // Static test to verify callbacks exhibit proper ownership
 /// # use discord_game_sdk::*;
@@ -60,6 +68,17 @@ pub struct Discord<'d, E>(pub(crate) *mut DiscordInner<'d, E>);
 
 impl<E> Drop for Discord<'_, E> {
     fn drop(&mut self) {
+        let delayed_callbacks = self
+            .inner()
+            .delayed_callbacks
+            .borrow_mut()
+            .drain(..)
+            .collect::<Vec<_>>();
+
+        for (_, callback) in delayed_callbacks {
+            callback(self, Err(Error::TransactionAborted));
+        }
+
         unsafe {
             let core = (*self.0).core;
             if !core.is_null() {
@@ -98,6 +117,18 @@ impl<'d, E> Discord<'d, E> {
     pub(crate) fn ref_copy(&self) -> DiscordRef<'d, E> {
         DiscordRef(ManuallyDrop::new(Discord(self.0)))
     }
+
+    // Cached by `on_current_user_update`, fetched until that event fires
+    pub(crate) fn current_user_id(&self) -> Option<UserID> {
+        if let Some(current_user_id) = self.inner().current_user_id.get() {
+            return Some(current_user_id);
+        }
+
+        let current_user_id = self.current_user().ok()?.id();
+        self.inner().current_user_id.set(Some(current_user_id));
+
+        Some(current_user_id)
+    }
 }
 
 impl<E: std::fmt::Debug> std::fmt::Debug for Discord<'_, E> {
@@ -106,11 +137,30 @@ impl<E: std::fmt::Debug> std::fmt::Debug for Discord<'_, E> {
     }
 }
 
+pub(crate) type OverlayClosedCallback<'d, E> = Box<dyn 'd + FnOnce(&Discord<'d, E>)>;
+pub(crate) type DelayedCallback<'d, E> = Box<dyn 'd + FnOnce(&Discord<'d, E>, Result<()>)>;
+pub(crate) type LogSink<'d> = Box<dyn 'd + Fn(log::Level, &str)>;
+
 pub(crate) struct DiscordInner<'d, E> {
     pub(crate) _invariant_lifetime: PhantomData<*mut &'d ()>,
 
     pub(crate) core: *mut sys::IDiscordCore,
     pub(crate) client_id: sys::DiscordClientId,
+    pub(crate) connected: bool,
+    pub(crate) auto_flush_network: bool,
+    pub(crate) pending_callbacks: Cell<usize>,
+    pub(crate) ticks: Cell<u64>,
+    pub(crate) delayed_callbacks: RefCell<Vec<(u64, DelayedCallback<'d, E>)>>,
+    pub(crate) relationships_ready: Cell<bool>,
+    pub(crate) current_user_id: Cell<Option<UserID>>,
+    pub(crate) overlay_opened: Cell<bool>,
+    pub(crate) lobby_search_generation: Cell<u64>,
+    pub(crate) lobby_speaking: RefCell<HashMap<LobbyID, HashMap<UserID, bool>>>,
+    pub(crate) connected_lobbies: RefCell<HashSet<LobbyID>>,
+    pub(crate) voice_lobbies: RefCell<HashSet<LobbyID>>,
+    pub(crate) folder_path: RefCell<Option<String>>,
+    pub(crate) overlay_closed_callbacks: RefCell<Vec<OverlayClosedCallback<'d, E>>>,
+    pub(crate) log_sink: Option<LogSink<'d>>,
     pub(crate) event_handler: UnsafeCell<Option<E>>,
 
     pub(crate) achievement_events: sys::IDiscordAchievementEvents,
@@ -132,6 +182,18 @@ impl<E> DiscordInner<'_, E> {
     pub(crate) fn event_handler_mut(&mut self) -> &mut Option<E> {
         unsafe { &mut *self.event_handler.get() }
     }
+
+    // The current user left the voice channel of the lobby
+    pub(crate) fn forget_lobby_voice(&self, lobby_id: LobbyID) {
+        self.voice_lobbies.borrow_mut().remove(&lobby_id);
+        self.lobby_speaking.borrow_mut().remove(&lobby_id);
+    }
+
+    // The current user left the lobby, or it was deleted
+    pub(crate) fn forget_lobby(&self, lobby_id: LobbyID) {
+        self.connected_lobbies.borrow_mut().remove(&lobby_id);
+        self.forget_lobby_voice(lobby_id);
+    }
 }
 
 impl<E: std::fmt::Debug> std::fmt::Debug for DiscordInner<'_, E> {
@@ -139,6 +201,27 @@ impl<E: std::fmt::Debug> std::fmt::Debug for DiscordInner<'_, E> {
         fmt.debug_struct("DiscordInner")
             .field("ffi_ptr", &self.core)
             .field("client_id", &self.client_id)
+            .field("connected", &self.connected)
+            .field("auto_flush_network", &self.auto_flush_network)
+            .field("pending_callbacks", &self.pending_callbacks.get())
+            .field("ticks", &self.ticks.get())
+            .field("delayed_callbacks", &self.delayed_callbacks.borrow().len())
+            .field("relationships_ready", &self.relationships_ready.get())
+            .field("current_user_id", &self.current_user_id.get())
+            .field("overlay_opened", &self.overlay_opened.get())
+            .field(
+                "lobby_search_generation",
+                &self.lobby_search_generation.get(),
+            )
+            .field("lobby_speaking", &self.lobby_speaking.borrow())
+            .field("connected_lobbies", &self.connected_lobbies.borrow())
+            .field("voice_lobbies", &self.voice_lobbies.borrow())
+            .field("folder_path", &self.folder_path.borrow())
+            .field(
+                "overlay_closed_callbacks",
+                &self.overlay_closed_callbacks.borrow().len(),
+            )
+            .field("log_sink", &self.log_sink.is_some())
             .field("event_handler", self.event_handler())
             .finish()
     }
@@ -15,7 +15,7 @@ pub enum EntitlementKind {
     PremiumSubscription,
     /// Entitlement was purchased
     Purchase,
-    /// Entitlement was purchased by a dev in application test mode<Paste>
+    /// Entitlement was purchased by a dev in application test mode
     TestModePurchase,
     /// Entitlement was gifted by another user
     UserGift,
@@ -1,5 +1,5 @@
 use crate::sys;
-use std::fmt;
+use std::{fmt, io};
 
 /// Alias for a `Result` with the error type [`discord_game_sdk::Error`]
 ///
@@ -144,6 +144,38 @@ pub enum Error {
     Undefined(sys::EDiscordResult),
 }
 
+impl Error {
+    /// Whether the operation may succeed if retried later, e.g. after backing off
+    /// from [`RateLimited`](#variant.RateLimited) or waiting for the Discord client to start.
+    ///
+    /// [`TransactionAborted`](#variant.TransactionAborted) is not transient: it is returned when
+    /// the `Discord` instance is dropped, or when a newer request supersedes an older one.
+    ///
+    /// ```rust
+    /// # use discord_game_sdk::*;
+    /// # fn example(discord: Discord<'_, ()>) -> Result<()> {
+    /// discord.write_file_async("save", b"data", |discord, result| match result {
+    ///     Err(error) if error.is_transient() => {
+    ///         // try again in a moment
+    ///     }
+    ///     Err(error) => eprintln!("failed to save: {}", error),
+    ///     Ok(()) => {}
+    /// });
+    /// # Ok(()) }
+    /// ```
+    // `matches!` requires Rust 1.42
+    #[allow(clippy::match_like_matches_macro)]
+    pub fn is_transient(&self) -> bool {
+        use Error::*;
+
+        match self {
+            ServiceUnavailable | LockFailed | NotRunning | RateLimited | SelectChannelTimeout
+            | GetGuildTimeout => true,
+            _ => false,
+        }
+    }
+}
+
 impl fmt::Display for Error {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         use Error::*;
@@ -200,3 +232,58 @@ impl fmt::Display for Error {
 }
 
 impl std::error::Error for Error {}
+
+impl From<Error> for io::Error {
+    fn from(error: Error) -> Self {
+        use Error::*;
+
+        let kind = match error {
+            NotFound => io::ErrorKind::NotFound,
+            InvalidPermissions
+            | NotAuthenticated
+            | InvalidAccessToken
+            | ApplicationMismatch
+            | UnauthorizedForAchievement => io::ErrorKind::PermissionDenied,
+            ServiceUnavailable | NotInstalled | NotRunning => io::ErrorKind::NotConnected,
+            Conflict => io::ErrorKind::AlreadyExists,
+            InvalidPayload | InvalidCommand | InvalidFilename | InvalidFileSize => {
+                io::ErrorKind::InvalidInput
+            }
+            InvalidDataUrl | InvalidBase64 => io::ErrorKind::InvalidData,
+            SelectChannelTimeout | GetGuildTimeout => io::ErrorKind::TimedOut,
+            _ => io::ErrorKind::Other,
+        };
+
+        io::Error::new(kind, error)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_transient() {
+        for error in &[
+            Error::ServiceUnavailable,
+            Error::LockFailed,
+            Error::NotRunning,
+            Error::RateLimited,
+            Error::GetGuildTimeout,
+        ] {
+            assert!(error.is_transient(), "{:?}", error);
+        }
+
+        for error in &[
+            Error::InvalidVersion,
+            Error::Internal,
+            Error::NotFound,
+            Error::InvalidAccessToken,
+            Error::NotInstalled,
+            Error::TransactionAborted,
+            Error::Undefined(100),
+        ] {
+            assert!(!error.is_transient(), "{:?}", error);
+        }
+    }
+}
@@ -91,6 +91,10 @@ pub trait EventHandler: Sized {
 
     /// Fires when a message is sent to the lobby.
     ///
+    /// Text messages sent with
+    /// [`Discord::send_lobby_text`](struct.Discord.html#method.send_lobby_text) can be decoded
+    /// with [`std::str::from_utf8`](https://doc.rust-lang.org/std/str/fn.from_utf8.html).
+    ///
     /// > [Method in official docs](https://discordapp.com/developers/docs/game-sdk/lobbies#onlobbymessage)
     fn on_lobby_message(
         &mut self,
@@ -150,7 +154,7 @@ pub trait EventHandler: Sized {
     /// Fires when the overlay is opened or closed.
     ///
     /// > [Method in official docs](https://discordapp.com/developers/docs/game-sdk/overlay#ontoggle)
-    fn on_overlay_toggle(&mut self, discord: &Discord<'_, Self>, closed: bool) {}
+    fn on_overlay_toggle(&mut self, discord: &Discord<'_, Self>, opened: bool) {}
 
     /// Fires at initialization when Discord<'_, Self> has cached a snapshot of all your relationships.
     ///
@@ -176,8 +180,10 @@ pub trait EventHandler: Sized {
 
     /// Fires when the User struct of the currently connected user changes.
     ///
+    /// The event is skipped if the current user could not be fetched.
+    ///
     /// > [Method in official docs](https://discordapp.com/developers/docs/game-sdk/users#oncurrentuserupdate)
-    fn on_current_user_update(&mut self, discord: &Discord<'_, Self>) {}
+    fn on_current_user_update(&mut self, discord: &Discord<'_, Self>, user: &User) {}
 
     /// Fires when the current user has updated their voice settings.
     fn on_voice_settings_update(&mut self, discord: &Discord<'_, Self>) {}
@@ -1,8 +1,8 @@
 use crate::{
     discord::{Discord, DiscordInner},
-    sys, utils, Activity, Entitlement, EventHandler, Relationship, User, UserAchievement,
+    sys, utils, EventHandler,
 };
-use std::{ffi::c_void, mem::ManuallyDrop};
+use std::{collections::HashMap, ffi::c_void, mem::ManuallyDrop, panic::AssertUnwindSafe};
 
 fn with_event_handler<E>(
     inner: *mut c_void,
@@ -39,7 +39,7 @@ pub(crate) fn achievement<E: EventHandler>() -> sys::IDiscordAchievementEvents {
             ) {
                 with_event_handler(inner, |eh: &mut E, discord| {
                     eh.on_user_achievement_update(discord, unsafe {
-                        &*(user_achievement as *const UserAchievement)
+                        utils::ptr_to_ref(user_achievement)
                     })
                 })
             }
@@ -80,7 +80,7 @@ pub(crate) fn activity<E: EventHandler>() -> sys::IDiscordActivityEvents {
                 user: *mut sys::DiscordUser,
             ) {
                 with_event_handler(inner, |eh: &mut E, discord| {
-                    eh.on_activity_join_request(discord, unsafe { &*(user as *const User) })
+                    eh.on_activity_join_request(discord, unsafe { utils::ptr_to_ref(user) })
                 })
             }
 
@@ -98,8 +98,8 @@ pub(crate) fn activity<E: EventHandler>() -> sys::IDiscordActivityEvents {
                     eh.on_activity_invite(
                         discord,
                         kind.into(),
-                        unsafe { &*(user as *const User) },
-                        unsafe { &*(activity as *const Activity) },
+                        unsafe { utils::ptr_to_ref(user) },
+                        unsafe { utils::ptr_to_ref(activity) },
                     )
                 })
             }
@@ -130,6 +130,14 @@ pub(crate) fn lobby<E: EventHandler>() -> sys::IDiscordLobbyEvents {
                 lobby_id: sys::DiscordLobbyId,
                 reason: u32,
             ) {
+                utils::abort_on_panic(|| {
+                    debug_assert!(!inner.is_null());
+
+                    let inner = unsafe { &*(inner as *const DiscordInner<'_, E>) };
+
+                    inner.forget_lobby(lobby_id);
+                });
+
                 with_event_handler(inner, |eh: &mut E, discord| {
                     eh.on_lobby_delete(discord, lobby_id, reason)
                 })
@@ -172,6 +180,21 @@ pub(crate) fn lobby<E: EventHandler>() -> sys::IDiscordLobbyEvents {
                 lobby_id: sys::DiscordLobbyId,
                 member_id: sys::DiscordUserId,
             ) {
+                utils::abort_on_panic(|| {
+                    debug_assert!(!inner.is_null());
+
+                    let discord = ManuallyDrop::new(Discord(inner as *mut DiscordInner<'_, E>));
+                    let inner = discord.inner();
+
+                    if discord.current_user_id() == Some(member_id) {
+                        inner.forget_lobby(lobby_id);
+                    } else if let Some(members) =
+                        inner.lobby_speaking.borrow_mut().get_mut(&lobby_id)
+                    {
+                        members.remove(&member_id);
+                    }
+                });
+
                 with_event_handler(inner, |eh: &mut E, discord| {
                     eh.on_member_disconnect(discord, lobby_id, member_id)
                 })
@@ -205,6 +228,19 @@ pub(crate) fn lobby<E: EventHandler>() -> sys::IDiscordLobbyEvents {
                 member_id: sys::DiscordUserId,
                 speaking: bool,
             ) {
+                utils::abort_on_panic(|| {
+                    debug_assert!(!inner.is_null());
+
+                    let inner = unsafe { &*(inner as *const DiscordInner<'_, E>) };
+
+                    inner
+                        .lobby_speaking
+                        .borrow_mut()
+                        .entry(lobby_id)
+                        .or_insert_with(HashMap::new)
+                        .insert(member_id, speaking);
+                });
+
                 with_event_handler(inner, |eh: &mut E, discord| {
                     eh.on_speaking(discord, lobby_id, member_id, speaking)
                 })
@@ -270,9 +306,38 @@ pub(crate) fn overlay<E: EventHandler>() -> sys::IDiscordOverlayEvents {
     sys::IDiscordOverlayEvents {
         on_toggle: {
             extern "C" fn on_toggle<E: EventHandler>(inner: *mut c_void, locked: bool) {
+                debug_assert!(!inner.is_null());
+
+                unsafe {
+                    (*(inner as *const DiscordInner<'_, E>))
+                        .overlay_opened
+                        .set(!locked);
+                }
+
                 with_event_handler(inner, |eh: &mut E, discord| {
                     eh.on_overlay_toggle(discord, !locked)
-                })
+                });
+
+                if !locked {
+                    return;
+                }
+
+                utils::abort_on_panic(|| {
+                    debug_assert!(!inner.is_null());
+
+                    let discord = ManuallyDrop::new(Discord(inner as *mut DiscordInner<'_, E>));
+
+                    let callbacks: Vec<_> = discord
+                        .inner()
+                        .overlay_closed_callbacks
+                        .borrow_mut()
+                        .drain(..)
+                        .collect();
+
+                    for callback in callbacks {
+                        utils::log_on_panic(AssertUnwindSafe(|| callback(&discord)));
+                    }
+                });
             }
 
             Some(on_toggle::<E>)
@@ -284,6 +349,14 @@ pub(crate) fn relationship<E: EventHandler>() -> sys::IDiscordRelationshipEvents
     sys::IDiscordRelationshipEvents {
         on_refresh: {
             extern "C" fn on_refresh<E: EventHandler>(inner: *mut c_void) {
+                debug_assert!(!inner.is_null());
+
+                unsafe {
+                    (*(inner as *const DiscordInner<'_, E>))
+                        .relationships_ready
+                        .set(true);
+                }
+
                 with_event_handler(inner, |eh: &mut E, discord| {
                     eh.on_relationships_refresh(discord)
                 })
@@ -298,9 +371,7 @@ pub(crate) fn relationship<E: EventHandler>() -> sys::IDiscordRelationshipEvents
                 relationship: *mut sys::DiscordRelationship,
             ) {
                 with_event_handler(inner, |eh: &mut E, discord| {
-                    eh.on_relationship_update(discord, unsafe {
-                        &*(relationship as *const Relationship)
-                    })
+                    eh.on_relationship_update(discord, unsafe { utils::ptr_to_ref(relationship) })
                 })
             }
 
@@ -317,9 +388,7 @@ pub(crate) fn store<E: EventHandler>() -> sys::IDiscordStoreEvents {
                 entitlement: *mut sys::DiscordEntitlement,
             ) {
                 with_event_handler(inner, |eh: &mut E, discord| {
-                    eh.on_entitlement_create(discord, unsafe {
-                        &*(entitlement as *const Entitlement)
-                    })
+                    eh.on_entitlement_create(discord, unsafe { utils::ptr_to_ref(entitlement) })
                 })
             }
 
@@ -332,9 +401,7 @@ pub(crate) fn store<E: EventHandler>() -> sys::IDiscordStoreEvents {
                 entitlement: *mut sys::DiscordEntitlement,
             ) {
                 with_event_handler(inner, |eh: &mut E, discord| {
-                    eh.on_entitlement_delete(discord, unsafe {
-                        &*(entitlement as *const Entitlement)
-                    })
+                    eh.on_entitlement_delete(discord, unsafe { utils::ptr_to_ref(entitlement) })
                 })
             }
 
@@ -347,8 +414,22 @@ pub(crate) fn user<E: EventHandler>() -> sys::IDiscordUserEvents {
     sys::IDiscordUserEvents {
         on_current_user_update: {
             extern "C" fn on_current_user_update<E: EventHandler>(inner: *mut c_void) {
-                with_event_handler(inner, |eh: &mut E, discord| {
-                    eh.on_current_user_update(discord)
+                let current_user = utils::abort_on_panic(|| {
+                    debug_assert!(!inner.is_null());
+
+                    let discord = ManuallyDrop::new(Discord(inner as *mut DiscordInner<'_, E>));
+                    let current_user = discord.current_user();
+
+                    if let Ok(ref user) = current_user {
+                        discord.inner().current_user_id.set(Some(user.id()));
+                    }
+
+                    current_user
+                });
+
+                with_event_handler(inner, |eh: &mut E, discord| match current_user {
+                    Ok(user) => eh.on_current_user_update(discord, &user),
+                    Err(error) => log::error!("failed to fetch current user: {}", error),
                 })
             }
 
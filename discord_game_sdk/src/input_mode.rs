@@ -1,7 +1,7 @@
 use crate::{
     sys,
     utils::{charbuf_to_str, write_charbuf},
-    InputModeKind,
+    Error, InputModeKind, Result,
 };
 
 /// Input Mode
@@ -25,6 +25,24 @@ impl InputMode {
         charbuf_to_str(&self.0.shortcut)
     }
 
+    /// The individual keys of [`shortcut`](#method.shortcut), empty if there is no shortcut.
+    ///
+    /// ```rust
+    /// # use discord_game_sdk::*;
+    /// let mode = InputMode::push_to_talk("ctrl + shift + v");
+    ///
+    /// assert_eq!(
+    ///     mode.shortcut_keys().collect::<Vec<_>>(),
+    ///     vec!["ctrl", "shift", "v"]
+    /// );
+    /// ```
+    pub fn shortcut_keys(&self) -> impl Iterator<Item = &str> {
+        self.shortcut()
+            .split('+')
+            .map(str::trim)
+            .filter(|key| !key.is_empty())
+    }
+
     /// Create a new Input Mode with kind [`VoiceActivity`].
     ///
     /// [`VoiceActivity`]: enum.InputModeKind.html#variant.VoiceActivity
@@ -35,21 +53,50 @@ impl InputMode {
         })
     }
 
-    /// Create a new Input Mode with kind [`PushToTalk`] and a shortcut.
+    /// Create a new Input Mode with kind [`PushToTalk`] and a shortcut,
+    /// either a raw string or a [`Shortcut`].
     ///
     /// Only the first 256 bytes will be written.
     ///
     /// [`PushToTalk`]: enum.InputModeKind.html#variant.PushToTalk
-    pub fn push_to_talk(shortcut: &str) -> Self {
+    /// [`Shortcut`]: struct.Shortcut.html
+    ///
+    /// ```rust
+    /// # use discord_game_sdk::*;
+    /// let mode = InputMode::push_to_talk(Shortcut::new().ctrl().key("v"));
+    ///
+    /// assert_eq!(mode.shortcut(), "ctrl + v");
+    /// ```
+    pub fn push_to_talk(shortcut: impl std::fmt::Display) -> Self {
         let mut mode = sys::DiscordInputMode {
             type_: sys::DiscordInputModeType_PushToTalk,
             ..sys::DiscordInputMode::default()
         };
 
-        write_charbuf(&mut mode.shortcut, shortcut);
+        write_charbuf(&mut mode.shortcut, &shortcut.to_string());
 
         Self(mode)
     }
+
+    // The SDK accepts push-to-talk without a shortcut, leaving the user unable to transmit,
+    // empty keys come from malformed shortcuts such as `ctrl + ` or `ctrl + +`
+    pub(crate) fn validate(&self) -> Result<()> {
+        match self.kind() {
+            InputModeKind::PushToTalk
+                if self.shortcut().split('+').any(|key| key.trim().is_empty()) =>
+            {
+                Err(Error::InvalidPayload)
+            }
+            InputModeKind::VoiceActivity if !self.shortcut().is_empty() => {
+                log::warn!(
+                    "shortcut {:?} is ignored with voice activity",
+                    self.shortcut()
+                );
+                Ok(())
+            }
+            _ => Ok(()),
+        }
+    }
 }
 
 impl std::fmt::Debug for InputMode {
@@ -60,3 +107,51 @@ impl std::fmt::Debug for InputMode {
             .finish()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Shortcut;
+
+    #[test]
+    fn test_validate() {
+        assert!(InputMode::voice_activity().validate().is_ok());
+        assert!(InputMode::push_to_talk("caps lock").validate().is_ok());
+        assert_eq!(
+            InputMode::push_to_talk("").validate(),
+            Err(Error::InvalidPayload)
+        );
+        assert_eq!(
+            InputMode::push_to_talk(" + ").validate(),
+            Err(Error::InvalidPayload)
+        );
+        assert_eq!(
+            InputMode::push_to_talk(Shortcut::new().ctrl().key(" ")).validate(),
+            Err(Error::InvalidPayload)
+        );
+        assert_eq!(
+            InputMode::push_to_talk(Shortcut::new().ctrl().key("+")).validate(),
+            Err(Error::InvalidPayload)
+        );
+        assert_eq!(
+            InputMode::push_to_talk(Shortcut::new().ctrl().key("v+")).validate(),
+            Err(Error::InvalidPayload)
+        );
+        assert!(InputMode::push_to_talk(Shortcut::new().ctrl().key("v"))
+            .validate()
+            .is_ok());
+
+        // Not an error, `+` separates keys
+        let mode = InputMode::push_to_talk(Shortcut::new().ctrl().key("shift+v"));
+        assert!(mode.validate().is_ok());
+        assert_eq!(
+            mode.shortcut_keys().collect::<Vec<_>>(),
+            vec!["ctrl", "shift", "v"]
+        );
+
+        let mut mode = InputMode::push_to_talk("caps lock");
+        mode.0.type_ = sys::DiscordInputModeType_VoiceActivity;
+
+        assert!(mode.validate().is_ok());
+    }
+}
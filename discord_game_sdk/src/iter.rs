@@ -1,60 +1,143 @@
-pub(crate) struct Collection<'r, I> {
-    getter: Box<dyn 'r + Fn(u32) -> I>,
-    count: u32,
+use crate::Result;
+
+// The collection may shrink during iteration, e.g. by deleting files,
+// when a getter fails, the count is fetched again to tell this apart from other errors
+pub(crate) struct Collection<'r, T> {
+    getter: Box<dyn 'r + Fn(u32) -> Result<T>>,
+    counter: Box<dyn 'r + Fn() -> u32>,
     index: u32,
-    back_index: u32,
+    end: u32,
 }
 
-impl<'r, I> Collection<'r, I> {
-    pub(crate) fn new(getter: Box<dyn 'r + Fn(u32) -> I>, count: u32) -> Self {
+impl<'r, T> Collection<'r, T> {
+    pub(crate) fn new(
+        getter: Box<dyn 'r + Fn(u32) -> Result<T>>,
+        count: u32,
+        counter: Box<dyn 'r + Fn() -> u32>,
+    ) -> Self {
         Self {
             getter,
-            count,
+            counter,
             index: 0,
-            back_index: 0,
+            end: count,
+        }
+    }
+
+    // Returns whether the collection shrank past the current end
+    fn recount(&mut self) -> bool {
+        let count = (self.counter)();
+
+        if count < self.end {
+            self.end = count;
+            true
+        } else {
+            false
         }
     }
 }
 
-impl<I> Iterator for Collection<'_, I> {
-    type Item = I;
+impl<T> Iterator for Collection<'_, T> {
+    type Item = Result<T>;
 
     fn next(&mut self) -> Option<Self::Item> {
-        if self.index + self.back_index < self.count {
-            self.index += 1;
-            Some((self.getter)(self.index - 1))
-        } else {
-            None
+        if self.index >= self.end {
+            return None;
+        }
+
+        match (self.getter)(self.index) {
+            Err(_) if self.recount() && self.index >= self.end => None,
+            item => {
+                self.index += 1;
+                Some(item)
+            }
         }
     }
 
+    // Only shrinks when `next` or `next_back` notice it, as `ExactSizeIterator` requires
     fn size_hint(&self) -> (usize, Option<usize>) {
-        (self.count as usize, Some(self.count as usize))
+        let remaining = self.end.saturating_sub(self.index) as usize;
+
+        (remaining, Some(remaining))
     }
 }
 
-impl<I> DoubleEndedIterator for Collection<'_, I> {
+impl<T> DoubleEndedIterator for Collection<'_, T> {
     fn next_back(&mut self) -> Option<Self::Item> {
-        if self.index + self.back_index < self.count {
-            self.back_index += 1;
-            Some((self.getter)(self.count - self.back_index))
-        } else {
-            None
+        while self.index < self.end {
+            match (self.getter)(self.end - 1) {
+                // The last items are gone, try again from the new end
+                Err(_) if self.recount() => continue,
+                item => {
+                    self.end -= 1;
+                    return Some(item);
+                }
+            }
         }
+
+        None
     }
 }
 
-impl<I> ExactSizeIterator for Collection<'_, I> {}
+impl<T> ExactSizeIterator for Collection<'_, T> {}
 
-impl<I> std::iter::FusedIterator for Collection<'_, I> {}
+impl<T> std::iter::FusedIterator for Collection<'_, T> {}
 
-impl<I> std::fmt::Debug for Collection<'_, I> {
+impl<T> std::fmt::Debug for Collection<'_, T> {
     fn fmt(&self, fmt: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         fmt.debug_struct("Collection")
             .field("getter", &(..))
-            .field("count", &self.count)
+            .field("counter", &(..))
             .field("index", &self.index)
-            .field("back_index", &self.back_index)
+            .field("end", &self.end)
             .finish()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Error;
+    use std::cell::Cell;
+
+    #[test]
+    fn errors_are_yielded() {
+        let mut iter = Collection::new(
+            Box::new(|i| if i == 1 { Err(Error::NotFound) } else { Ok(i) }),
+            3,
+            Box::new(|| 3),
+        );
+
+        assert_eq!(iter.len(), 3);
+        assert_eq!(iter.next(), Some(Ok(0)));
+        assert_eq!(iter.next(), Some(Err(Error::NotFound)));
+        assert_eq!(iter.len(), 1);
+        assert_eq!(iter.next(), Some(Ok(2)));
+        assert_eq!(iter.next(), None);
+    }
+
+    #[test]
+    fn shrinking() {
+        let count = Cell::new(4);
+
+        let mut iter = Collection::new(
+            Box::new(|i| {
+                if i < count.get() {
+                    Ok(i)
+                } else {
+                    Err(Error::NotFound)
+                }
+            }),
+            count.get(),
+            Box::new(|| count.get()),
+        );
+
+        assert_eq!(iter.next(), Some(Ok(0)));
+
+        count.set(2);
+        assert_eq!(iter.len(), 3);
+        assert_eq!(iter.next_back(), Some(Ok(1)));
+        assert_eq!(iter.len(), 0);
+        assert_eq!(iter.next(), None);
+        assert_eq!(iter.next_back(), None);
+    }
+}
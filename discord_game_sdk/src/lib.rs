@@ -63,6 +63,13 @@
 //! This allows for `cargo run` to function.
 //!
 //!
+//! ### [`flate2`](https://docs.rs/flate2)
+//!
+//! Optional crate.
+//!
+//! Provides `Discord::write_file_compressed` and `Discord::read_file_compressed`.
+//!
+//!
 //! ### [`image`](https://docs.rs/image)
 //!
 //! Optional crate.
@@ -116,10 +123,12 @@
 
 mod action;
 mod activity;
+mod activity_controller;
 mod activity_kind;
 mod aliases;
 mod cast;
 mod comparison;
+mod connect_options;
 mod create_flags;
 mod discord;
 mod distance;
@@ -140,6 +149,7 @@ mod lobby;
 mod lobby_kind;
 mod lobby_member_transaction;
 mod lobby_transaction;
+mod locale;
 mod oauth2_token;
 mod premium_kind;
 mod presence;
@@ -148,9 +158,14 @@ mod relationship_kind;
 mod reliability;
 mod request_reply;
 mod search_query;
+mod shortcut;
 mod sku;
 mod sku_kind;
+mod sku_price;
+mod sort_key;
 mod status;
+mod storage_error;
+mod storage_key;
 mod to_result;
 mod user;
 mod user_achievement;
@@ -184,10 +199,12 @@ pub(crate) use discord_game_sdk_sys as sys;
 pub use self::{
     action::Action,
     activity::Activity,
+    activity_controller::ActivityController,
     activity_kind::ActivityKind,
     aliases::*,
     cast::Cast,
     comparison::Comparison,
+    connect_options::ConnectOptions,
     create_flags::CreateFlags,
     discord::Discord,
     distance::Distance,
@@ -206,6 +223,7 @@ pub use self::{
     lobby_kind::LobbyKind,
     lobby_member_transaction::LobbyMemberTransaction,
     lobby_transaction::LobbyTransaction,
+    locale::Locale,
     oauth2_token::OAuth2Token,
     premium_kind::PremiumKind,
     presence::Presence,
@@ -214,9 +232,14 @@ pub use self::{
     reliability::Reliability,
     request_reply::RequestReply,
     search_query::SearchQuery,
+    shortcut::Shortcut,
     sku::Sku,
     sku_kind::SkuKind,
+    sku_price::SkuPrice,
+    sort_key::SortKey,
     status::Status,
+    storage_error::StorageError,
+    storage_key::StorageKey,
     user::User,
     user_achievement::UserAchievement,
     user_flags::UserFlags,
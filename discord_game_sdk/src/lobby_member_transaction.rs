@@ -45,7 +45,22 @@ impl LobbyMemberTransaction {
     /// A nul byte will be appended to `key` if one is not present.
     ///
     /// > [Method in official docs](https://discordapp.com/developers/docs/game-sdk/lobbies#lobbymembertransactiondeletemetadata)
-    pub fn delete_metadata<S>(&mut self, mut key: String) -> &mut Self {
+    /// ```rust
+    /// # use discord_game_sdk::*;
+    /// # fn example(discord: Discord<'_, ()>, lobby_id: LobbyID, user_id: UserID) -> Result<()> {
+    /// discord.update_member(
+    ///     lobby_id,
+    ///     user_id,
+    ///     LobbyMemberTransaction::new().delete_metadata("ready".to_string()),
+    ///     |discord, result| {
+    ///         if let Err(error) = result {
+    ///             eprintln!("failed to update member: {}", error);
+    ///         }
+    ///     },
+    /// );
+    /// # Ok(()) }
+    /// ```
+    pub fn delete_metadata(&mut self, mut key: String) -> &mut Self {
         if !key.ends_with('\0') {
             key.push('\0')
         }
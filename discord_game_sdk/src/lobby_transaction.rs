@@ -73,7 +73,21 @@ impl LobbyTransaction {
     /// A nul byte will be appended to `key` if one is not present.
     ///
     /// > [Method in official docs](https://discordapp.com/developers/docs/game-sdk/lobbies#lobbytransactiondeletemetadata)
-    pub fn delete_metadata<S>(&mut self, mut key: String) -> &mut Self {
+    /// ```rust
+    /// # use discord_game_sdk::*;
+    /// # fn example(discord: Discord<'_, ()>, lobby_id: LobbyID) -> Result<()> {
+    /// discord.update_lobby(
+    ///     lobby_id,
+    ///     LobbyTransaction::new().delete_metadata("match_id".to_string()),
+    ///     |discord, result| {
+    ///         if let Err(error) = result {
+    ///             eprintln!("failed to update lobby: {}", error);
+    ///         }
+    ///     },
+    /// );
+    /// # Ok(()) }
+    /// ```
+    pub fn delete_metadata(&mut self, mut key: String) -> &mut Self {
         if !key.ends_with('\0') {
             key.push('\0')
         }
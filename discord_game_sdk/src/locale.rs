@@ -0,0 +1,203 @@
+/// Locale supported by the Discord client
+///
+/// > [Accepted locales in official docs](https://discordapp.com/developers/docs/dispatch/field-values#predefined-field-values-accepted-locales)
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum Locale {
+    /// Bulgarian (`bg`)
+    Bulgarian,
+    /// Chinese, China (`zh-CN`)
+    ChineseChina,
+    /// Chinese, Taiwan (`zh-TW`)
+    ChineseTaiwan,
+    /// Croatian (`hr`)
+    Croatian,
+    /// Czech (`cs`)
+    Czech,
+    /// Danish (`da`)
+    Danish,
+    /// Dutch (`nl`)
+    Dutch,
+    /// English, UK (`en-GB`)
+    EnglishUK,
+    /// English, US (`en-US`)
+    EnglishUS,
+    /// Finnish (`fi`)
+    Finnish,
+    /// French (`fr`)
+    French,
+    /// German (`de`)
+    German,
+    /// Greek (`el`)
+    Greek,
+    /// Hindi (`hi`)
+    Hindi,
+    /// Hungarian (`hu`)
+    Hungarian,
+    /// Indonesian (`id`)
+    Indonesian,
+    /// Italian (`it`)
+    Italian,
+    /// Japanese (`ja`)
+    Japanese,
+    /// Korean (`ko`)
+    Korean,
+    /// Lithuanian (`lt`)
+    Lithuanian,
+    /// Norwegian (`no`)
+    Norwegian,
+    /// Polish (`pl`)
+    Polish,
+    /// Portuguese, Brazilian (`pt-BR`)
+    PortugueseBrazil,
+    /// Romanian (`ro`)
+    Romanian,
+    /// Russian (`ru`)
+    Russian,
+    /// Spanish, Latin America (`es-419`)
+    SpanishLatinAmerica,
+    /// Spanish (`es-ES`)
+    SpanishSpain,
+    /// Swedish (`sv-SE`)
+    Swedish,
+    /// Thai (`th`)
+    Thai,
+    /// Turkish (`tr`)
+    Turkish,
+    /// Ukrainian (`uk`)
+    Ukrainian,
+    /// Vietnamese (`vi`)
+    Vietnamese,
+}
+
+impl Locale {
+    /// Parses a locale code, as returned by
+    /// [`Discord::current_locale`](struct.Discord.html#method.current_locale)
+    pub fn from_code(code: &str) -> Option<Self> {
+        Some(match code {
+            "bg" => Self::Bulgarian,
+            "zh-CN" => Self::ChineseChina,
+            "zh-TW" => Self::ChineseTaiwan,
+            "hr" => Self::Croatian,
+            "cs" => Self::Czech,
+            "da" => Self::Danish,
+            "nl" => Self::Dutch,
+            "en-GB" => Self::EnglishUK,
+            "en-US" => Self::EnglishUS,
+            "fi" => Self::Finnish,
+            "fr" => Self::French,
+            "de" => Self::German,
+            "el" => Self::Greek,
+            "hi" => Self::Hindi,
+            "hu" => Self::Hungarian,
+            "id" => Self::Indonesian,
+            "it" => Self::Italian,
+            "ja" => Self::Japanese,
+            "ko" => Self::Korean,
+            "lt" => Self::Lithuanian,
+            "no" => Self::Norwegian,
+            "pl" => Self::Polish,
+            "pt-BR" => Self::PortugueseBrazil,
+            "ro" => Self::Romanian,
+            "ru" => Self::Russian,
+            "es-419" => Self::SpanishLatinAmerica,
+            "es-ES" => Self::SpanishSpain,
+            "sv-SE" => Self::Swedish,
+            "th" => Self::Thai,
+            "tr" => Self::Turkish,
+            "uk" => Self::Ukrainian,
+            "vi" => Self::Vietnamese,
+            _ => return None,
+        })
+    }
+
+    /// The locale code, e.g. `en-US`
+    pub fn code(self) -> &'static str {
+        match self {
+            Self::Bulgarian => "bg",
+            Self::ChineseChina => "zh-CN",
+            Self::ChineseTaiwan => "zh-TW",
+            Self::Croatian => "hr",
+            Self::Czech => "cs",
+            Self::Danish => "da",
+            Self::Dutch => "nl",
+            Self::EnglishUK => "en-GB",
+            Self::EnglishUS => "en-US",
+            Self::Finnish => "fi",
+            Self::French => "fr",
+            Self::German => "de",
+            Self::Greek => "el",
+            Self::Hindi => "hi",
+            Self::Hungarian => "hu",
+            Self::Indonesian => "id",
+            Self::Italian => "it",
+            Self::Japanese => "ja",
+            Self::Korean => "ko",
+            Self::Lithuanian => "lt",
+            Self::Norwegian => "no",
+            Self::Polish => "pl",
+            Self::PortugueseBrazil => "pt-BR",
+            Self::Romanian => "ro",
+            Self::Russian => "ru",
+            Self::SpanishLatinAmerica => "es-419",
+            Self::SpanishSpain => "es-ES",
+            Self::Swedish => "sv-SE",
+            Self::Thai => "th",
+            Self::Turkish => "tr",
+            Self::Ukrainian => "uk",
+            Self::Vietnamese => "vi",
+        }
+    }
+}
+
+impl std::fmt::Display for Locale {
+    fn fmt(&self, fmt: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(fmt, "{}", self.code())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_locale_round_trip() {
+        for &locale in &[
+            Locale::Bulgarian,
+            Locale::ChineseChina,
+            Locale::ChineseTaiwan,
+            Locale::Croatian,
+            Locale::Czech,
+            Locale::Danish,
+            Locale::Dutch,
+            Locale::EnglishUK,
+            Locale::EnglishUS,
+            Locale::Finnish,
+            Locale::French,
+            Locale::German,
+            Locale::Greek,
+            Locale::Hindi,
+            Locale::Hungarian,
+            Locale::Indonesian,
+            Locale::Italian,
+            Locale::Japanese,
+            Locale::Korean,
+            Locale::Lithuanian,
+            Locale::Norwegian,
+            Locale::Polish,
+            Locale::PortugueseBrazil,
+            Locale::Romanian,
+            Locale::Russian,
+            Locale::SpanishLatinAmerica,
+            Locale::SpanishSpain,
+            Locale::Swedish,
+            Locale::Thai,
+            Locale::Turkish,
+            Locale::Ukrainian,
+            Locale::Vietnamese,
+        ] {
+            assert_eq!(Locale::from_code(locale.code()), Some(locale));
+        }
+
+        assert_eq!(Locale::from_code("en"), None);
+    }
+}
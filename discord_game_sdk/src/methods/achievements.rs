@@ -1,5 +1,5 @@
 use crate::{iter, sys, to_result::ToResult, Discord, Result, Snowflake, UserAchievement};
-use std::convert::TryInto;
+use std::{cell::RefCell, convert::TryInto, rc::Rc};
 
 /// # Achievements
 ///
@@ -50,6 +50,54 @@ impl<'d, E> Discord<'d, E> {
         }
     }
 
+    /// Updates the current user's completion for several achievements at once.
+    ///
+    /// `callback` is called once, after all updates have completed or with the first error.
+    ///
+    /// ```rust
+    /// # use discord_game_sdk::*;
+    /// # fn example(discord: Discord<'_, ()>) -> Result<()> {
+    /// discord.set_user_achievements(&[(1, 100), (2, 100), (3, 50)], |discord, result| {
+    ///     if let Err(error) = result {
+    ///         eprintln!("failed setting user achievements: {}", error);
+    ///     }
+    /// });
+    /// # Ok(()) }
+    /// ```
+    pub fn set_user_achievements(
+        &self,
+        updates: &[(Snowflake, u8)],
+        callback: impl 'd + FnOnce(&Discord<'d, E>, Result<()>),
+    ) {
+        if updates.is_empty() {
+            return callback(self, Ok(()));
+        }
+
+        let state = Rc::new(RefCell::new((updates.len(), Some(callback))));
+
+        for &(achievement_id, percent_complete) in updates {
+            let state = state.clone();
+
+            self.set_user_achievement(achievement_id, percent_complete, move |discord, res| {
+                let callback = {
+                    let (remaining, callback) = &mut *state.borrow_mut();
+
+                    *remaining -= 1;
+
+                    if res.is_err() || *remaining == 0 {
+                        callback.take()
+                    } else {
+                        None
+                    }
+                };
+
+                if let Some(callback) = callback {
+                    callback(discord, res);
+                }
+            });
+        }
+    }
+
     /// Loads the current user's achievements.
     ///
     /// The user achievements will remain loaded after `callback` returns.
@@ -198,6 +246,7 @@ impl<'d, E> Discord<'d, E> {
         iter::Collection::new(
             Box::new(move |i| self.ref_copy().user_achievement_at(i)),
             self.user_achievement_count(),
+            Box::new(move || self.ref_copy().user_achievement_count()),
         )
     }
 }
@@ -1,4 +1,4 @@
-use crate::{sys, to_result::ToResult, utils, Discord, OAuth2Token, Result};
+use crate::{sys, to_result::ToResult, utils, Discord, Locale, OAuth2Token, Result};
 use std::mem::size_of;
 
 /// # Applications
@@ -29,6 +29,19 @@ impl<'d, E> Discord<'d, E> {
         utils::charbuf_to_str(&locale).to_string()
     }
 
+    /// The locale that was set by the current user in their Discord settings,
+    /// `None` if it is not one of the locales known to this crate.
+    ///
+    /// ```rust
+    /// # use discord_game_sdk::*;
+    /// # fn example(discord: Discord<'_, ()>) -> Result<()> {
+    /// let locale = discord.current_locale_parsed().unwrap_or(Locale::EnglishUS);
+    /// # Ok(()) }
+    /// ```
+    pub fn current_locale_parsed(&self) -> Option<Locale> {
+        Locale::from_code(&self.current_locale())
+    }
+
     /// Get the name of pushed branch on which the game is running.
     ///
     /// These are branches that you created and pushed using
@@ -1,4 +1,4 @@
-use crate::{utils, Discord};
+use crate::{utils, Discord, Result};
 use std::{ffi::c_void, panic::UnwindSafe};
 
 impl<'d, E> Discord<'d, E> {
@@ -7,7 +7,7 @@ impl<'d, E> Discord<'d, E> {
         callback: impl 'd + FnOnce(&Discord<'d, E>, A),
     ) -> (*mut c_void, Option<unsafe extern "C" fn(*mut c_void, A)>) {
         extern "C" fn one_param_from_c<F: FnOnce(A), A: UnwindSafe>(ptr: *mut c_void, a: A) {
-            utils::abort_on_panic(|| {
+            utils::log_on_panic(|| {
                 // SAFETY:
                 // lifetime of F was ellided when it was turned into a raw pointer
                 // in all method calls, F is bound to 'd, which is the lifetime of the Discord instance
@@ -28,7 +28,11 @@ impl<'d, E> Discord<'d, E> {
         }
 
         let dref = self.ref_copy();
-        one_param_align_types(move |a| callback(&*dref, a))
+        self.track_pending_callback();
+        one_param_align_types(move |a| {
+            dref.untrack_pending_callback();
+            callback(&*dref, a)
+        })
     }
 
     pub(crate) fn two_params<A: UnwindSafe, B: UnwindSafe>(
@@ -40,7 +44,7 @@ impl<'d, E> Discord<'d, E> {
             a: A,
             b: B,
         ) {
-            utils::abort_on_panic(|| {
+            utils::log_on_panic(|| {
                 // SAFETY: see `one_param`
                 let callback = unsafe { Box::from_raw(ptr as *mut F) };
                 callback(a, b)
@@ -57,7 +61,11 @@ impl<'d, E> Discord<'d, E> {
         }
 
         let dref = self.ref_copy();
-        two_params_align_types(move |a, b| callback(&*dref, a, b))
+        self.track_pending_callback();
+        two_params_align_types(move |a, b| {
+            dref.untrack_pending_callback();
+            callback(&*dref, a, b)
+        })
     }
 
     pub(crate) fn three_params<A: UnwindSafe, B: UnwindSafe, C: UnwindSafe>(
@@ -78,7 +86,7 @@ impl<'d, E> Discord<'d, E> {
             b: B,
             c: C,
         ) {
-            utils::abort_on_panic(|| {
+            utils::log_on_panic(|| {
                 // SAFETY: see `one_param`
                 let callback = unsafe { Box::from_raw(ptr as *mut F) };
                 callback(a, b, c)
@@ -103,6 +111,57 @@ impl<'d, E> Discord<'d, E> {
         }
 
         let dref = self.ref_copy();
-        three_params_align_types(move |a, b, c| callback(&*dref, a, b, c))
+        self.track_pending_callback();
+        three_params_align_types(move |a, b, c| {
+            dref.untrack_pending_callback();
+            callback(&*dref, a, b, c)
+        })
+    }
+
+    // Calls `callback` with `Ok(())` during the `ticks`-th next call to `run_callbacks`,
+    // or with `Err(TransactionAborted)` if the instance is dropped first
+    pub(crate) fn after_ticks(
+        &self,
+        ticks: u64,
+        callback: impl 'd + FnOnce(&Discord<'d, E>, Result<()>),
+    ) {
+        self.track_pending_callback();
+
+        let deadline = self.inner().ticks.get() + ticks.max(1);
+
+        self.inner()
+            .delayed_callbacks
+            .borrow_mut()
+            .push((deadline, Box::new(callback)));
+    }
+
+    pub(crate) fn run_delayed_callbacks(&self) {
+        let ticks = self.inner().ticks.get() + 1;
+        self.inner().ticks.set(ticks);
+
+        let (due, delayed): (Vec<_>, Vec<_>) = self
+            .inner()
+            .delayed_callbacks
+            .borrow_mut()
+            .drain(..)
+            .partition(|(deadline, _)| *deadline <= ticks);
+
+        // Callbacks may delay more callbacks
+        self.inner().delayed_callbacks.borrow_mut().extend(delayed);
+
+        for (_, callback) in due {
+            self.untrack_pending_callback();
+            callback(self, Ok(()));
+        }
+    }
+
+    fn track_pending_callback(&self) {
+        let pending = &self.inner().pending_callbacks;
+        pending.set(pending.get() + 1);
+    }
+
+    fn untrack_pending_callback(&self) {
+        let pending = &self.inner().pending_callbacks;
+        pending.set(pending.get() - 1);
     }
 }
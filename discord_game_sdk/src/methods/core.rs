@@ -2,9 +2,15 @@ use crate::{
     discord::{Discord, DiscordInner},
     events, sys,
     to_result::ToResult,
-    utils, ClientID, CreateFlags, EventHandler, Result,
+    utils, ClientID, CreateFlags, Error, EventHandler, Result,
+};
+use std::{
+    cell::{Cell, RefCell, UnsafeCell},
+    collections::{HashMap, HashSet},
+    convert::TryFrom,
+    marker::PhantomData,
+    time::{Duration, Instant},
 };
-use std::{cell::UnsafeCell, convert::TryFrom, marker::PhantomData};
 
 /// # Core
 ///
@@ -30,7 +36,7 @@ use std::{cell::UnsafeCell, convert::TryFrom, marker::PhantomData};
 /// }
 /// # }
 /// ```
-impl<E> Discord<'_, E> {
+impl<'d, E> Discord<'d, E> {
     /// Calls [`with_create_flags`] with [`CreateFlags::Default`].
     ///
     /// [`with_create_flags`]: #method.with_create_flags
@@ -46,6 +52,31 @@ impl<E> Discord<'_, E> {
     ///
     /// SDK log messages are forwarded to [`log`](https://docs.rs/log)
     ///
+    /// ## Errors
+    ///
+    /// Setup failures are reported as distinct [`Error`](enum.Error.html) variants:
+    ///
+    /// - [`NotInstalled`](enum.Error.html#variant.NotInstalled): Discord is not installed
+    /// - [`NotRunning`](enum.Error.html#variant.NotRunning): Discord is installed but not running
+    /// - [`InvalidVersion`](enum.Error.html#variant.InvalidVersion): the SDK library does not
+    ///   match the version these bindings were generated for, see [`sdk_version`](#method.sdk_version)
+    ///
+    /// The SDK library itself is linked when the program is loaded, if it is missing the program
+    /// fails to start rather than this returning an error.
+    ///
+    /// ```rust
+    /// # use discord_game_sdk::*;
+    /// # const DISCORD_CLIENT_ID: ClientID = 0;
+    /// # fn example() {
+    /// match Discord::<()>::with_create_flags(DISCORD_CLIENT_ID, CreateFlags::NoRequireDiscord) {
+    ///     Ok(discord) => { /* ... */ }
+    ///     Err(Error::NotInstalled) => eprintln!("Discord is not installed"),
+    ///     Err(Error::InvalidVersion) => eprintln!("please update the game"),
+    ///     Err(error) => eprintln!("failed to start Discord integration: {}", error),
+    /// }
+    /// # }
+    /// ```
+    ///
     /// > [`Create` in official docs](https://discordapp.com/developers/docs/game-sdk/discord#create)  
     /// > [`SetLogHook` in official docs](https://discordapp.com/developers/docs/game-sdk/discord#setloghook)
     pub fn with_create_flags(client_id: ClientID, flags: CreateFlags) -> Result<Self>
@@ -71,6 +102,21 @@ impl<E> Discord<'_, E> {
             // SAFETY: overwritten by `sys::DiscordCreate`, not deref'd until then
             core: std::ptr::null_mut(),
             client_id,
+            connected: true,
+            auto_flush_network: false,
+            pending_callbacks: Cell::new(0),
+            ticks: Cell::new(0),
+            delayed_callbacks: RefCell::new(Vec::new()),
+            relationships_ready: Cell::new(false),
+            current_user_id: Cell::new(None),
+            overlay_opened: Cell::new(false),
+            lobby_search_generation: Cell::new(0),
+            lobby_speaking: RefCell::new(HashMap::new()),
+            connected_lobbies: RefCell::new(HashSet::new()),
+            voice_lobbies: RefCell::new(HashSet::new()),
+            folder_path: RefCell::new(None),
+            overlay_closed_callbacks: RefCell::new(Vec::new()),
+            log_sink: None,
             event_handler: UnsafeCell::new(None),
 
             achievement_events: events::achievement::<E>(),
@@ -97,9 +143,12 @@ impl<E> Discord<'_, E> {
 
         log::trace!("received pointer to {:p}", instance.inner().core);
 
-        instance.set_log_hook();
+        instance.set_log_hook(sys::DiscordLogLevel_Debug);
         instance.kickstart_managers();
 
+        let overlay_opened = instance.overlay_opened();
+        instance.inner().overlay_opened.set(overlay_opened);
+
         Ok(instance)
     }
 
@@ -155,13 +204,57 @@ impl<E> Discord<'_, E> {
         }
     }
 
-    fn set_log_hook(&self) {
-        extern "C" fn log_hook(
-            _: *mut std::ffi::c_void,
+    /// Sets the minimum level of SDK log messages forwarded to [`log`](https://docs.rs/log),
+    /// all levels are forwarded by default.
+    ///
+    /// The SDK does not log at the `Trace` level, and always forwards `Error` messages,
+    /// even with `LevelFilter::Off`.
+    ///
+    /// > [Method in official docs](https://discordapp.com/developers/docs/game-sdk/discord#setloghook)
+    ///
+    /// ```rust
+    /// # use discord_game_sdk::*;
+    /// # fn example(discord: Discord<'_, ()>) -> Result<()> {
+    /// discord.set_log_level(log::LevelFilter::Warn);
+    /// # Ok(()) }
+    /// ```
+    pub fn set_log_level(&self, level: log::LevelFilter) {
+        self.set_log_hook(match level {
+            log::LevelFilter::Off | log::LevelFilter::Error => sys::DiscordLogLevel_Error,
+            log::LevelFilter::Warn => sys::DiscordLogLevel_Warn,
+            log::LevelFilter::Info => sys::DiscordLogLevel_Info,
+            log::LevelFilter::Debug | log::LevelFilter::Trace => sys::DiscordLogLevel_Debug,
+        })
+    }
+
+    /// Forwards SDK log messages to `sink` instead of [`log`](https://docs.rs/log),
+    /// e.g. to route them through an engine's own logging.
+    ///
+    /// The minimum level set by [`set_log_level`](#method.set_log_level) still applies.
+    ///
+    /// ```rust
+    /// # use discord_game_sdk::*;
+    /// # fn example(mut discord: Discord<'_, ()>) -> Result<()> {
+    /// discord.set_log_sink(|level, message| {
+    ///     eprintln!("[discord] {}: {}", level, message);
+    /// });
+    /// # Ok(()) }
+    /// ```
+    pub fn set_log_sink(&mut self, sink: impl 'd + Fn(log::Level, &str)) {
+        self.inner_mut().log_sink = Some(Box::new(sink));
+    }
+
+    fn set_log_hook(&self, min_level: sys::EDiscordLogLevel) {
+        extern "C" fn log_hook<E>(
+            inner: *mut std::ffi::c_void,
             level: sys::EDiscordLogLevel,
             message: *const u8,
         ) {
             utils::abort_on_panic(|| {
+                debug_assert!(!inner.is_null());
+
+                let inner = unsafe { &*(inner as *const DiscordInner<'_, E>) };
+
                 let level = match level {
                     sys::DiscordLogLevel_Error => log::Level::Error,
                     sys::DiscordLogLevel_Warn => log::Level::Warn,
@@ -170,17 +263,21 @@ impl<E> Discord<'_, E> {
                     _ => log::Level::Trace,
                 };
 
-                log::log!(level, "SDK: {}", unsafe { utils::charptr_to_str(message) });
+                let message = unsafe { utils::charptr_to_str(message) };
+
+                match inner.log_sink {
+                    Some(ref sink) => sink(level, message),
+                    None => log::log!(level, "SDK: {}", message),
+                }
             })
         }
 
         unsafe {
             (*self.inner().core).set_log_hook.unwrap()(
                 self.inner().core,
-                sys::DiscordLogLevel_Debug,
-                // SAFETY: this is never used
-                std::ptr::null_mut(),
-                Some(log_hook),
+                min_level,
+                self.0 as *mut std::ffi::c_void,
+                Some(log_hook::<E>),
             );
         }
     }
@@ -213,10 +310,128 @@ impl<E> Discord<'_, E> {
     ///
     /// If the Discord client was closed, [`Error::NotRunning`](enum.Error.html#variant.NotRunning) will be returned.
     ///
+    /// If [`set_auto_flush_network`](#method.set_auto_flush_network) is enabled, errors from
+    /// flushing the network are also returned.
+    ///
     /// > [Method in official docs](https://discordapp.com/developers/docs/game-sdk/discord#runcallbacks)
     // We require &mut self to prevent calling during callbacks
     pub fn run_callbacks(&mut self) -> Result<()> {
-        unsafe { (*self.inner().core).run_callbacks.unwrap()(self.inner().core).to_result() }
+        let result =
+            unsafe { (*self.inner().core).run_callbacks.unwrap()(self.inner().core).to_result() };
+
+        self.run_delayed_callbacks();
+
+        match result {
+            Ok(()) => self.inner_mut().connected = true,
+            Err(Error::NotRunning) => self.inner_mut().connected = false,
+            Err(_) => {}
+        }
+
+        result?;
+
+        if self.inner().auto_flush_network {
+            self.flush_network()?;
+        }
+
+        Ok(())
+    }
+
+    /// Whether the Discord client was running during the last call to
+    /// [`run_callbacks`](#method.run_callbacks).
+    ///
+    /// ```rust
+    /// # use discord_game_sdk::*;
+    /// # fn example(mut discord: Discord<'_, ()>) -> Result<()> {
+    /// let _ = discord.run_callbacks();
+    ///
+    /// if !discord.is_connected() {
+    ///     // show "Discord not running" and retry later
+    /// }
+    /// # Ok(()) }
+    /// ```
+    pub fn is_connected(&self) -> bool {
+        self.inner().connected
+    }
+
+    /// Runs callbacks until all pending callbacks have been called, or `timeout` has elapsed.
+    ///
+    /// Useful before exiting, to make sure operations such as
+    /// [`write_file_async`](#method.write_file_async) have completed.
+    ///
+    /// Returns `Ok(true)` once no callbacks are pending, or `Ok(false)` if `timeout` elapsed
+    /// while some were still pending; those may still be called by later
+    /// [`run_callbacks`](#method.run_callbacks).
+    ///
+    /// ## Errors
+    ///
+    /// Stops waiting and returns the first error from [`run_callbacks`](#method.run_callbacks),
+    /// even if callbacks are still pending.
+    ///
+    /// ```rust
+    /// # use discord_game_sdk::*;
+    /// # fn example(mut discord: Discord<'_, ()>) -> Result<()> {
+    /// if !discord.drain_pending(std::time::Duration::from_secs(5))? {
+    ///     eprintln!("some operations did not complete in time");
+    /// }
+    /// # Ok(()) }
+    /// ```
+    pub fn drain_pending(&mut self, timeout: Duration) -> Result<bool> {
+        let start = Instant::now();
+
+        while self.inner().pending_callbacks.get() > 0 {
+            if start.elapsed() >= timeout {
+                return Ok(false);
+            }
+
+            self.run_callbacks()?;
+
+            std::thread::sleep(Duration::from_millis(1));
+        }
+
+        Ok(true)
+    }
+
+    /// Runs callbacks repeatedly until `duration` has elapsed,
+    /// for applications that are not driven by a game loop.
+    ///
+    /// ## Errors
+    ///
+    /// Returns early with the first error, see [`run_callbacks`](#method.run_callbacks).
+    ///
+    /// ```rust
+    /// # use discord_game_sdk::*;
+    /// # fn example(mut discord: Discord<'_, ()>) -> Result<()> {
+    /// loop {
+    ///     discord.pump_for(std::time::Duration::from_secs(1))?;
+    ///     // ...
+    /// }
+    /// # Ok(()) }
+    /// ```
+    pub fn pump_for(&mut self, duration: Duration) -> Result<()> {
+        let start = Instant::now();
+
+        while start.elapsed() < duration {
+            self.run_callbacks()?;
+
+            std::thread::sleep(Duration::from_millis(1));
+        }
+
+        Ok(())
+    }
+
+    /// The version of the Discord Game SDK this crate was built against,
+    /// as passed to `DiscordCreate`.
+    ///
+    /// Useful to include in bug reports.
+    ///
+    /// ```rust
+    /// # use discord_game_sdk::*;
+    /// # fn example() {
+    /// println!("Discord Game SDK version {}", Discord::<()>::sdk_version());
+    /// # }
+    /// ```
+    pub fn sdk_version() -> i32 {
+        sys::DISCORD_VERSION
     }
 
     pub(crate) unsafe fn achievement_manager(&self) -> *mut sys::IDiscordAchievementManager {
@@ -266,4 +481,136 @@ impl<E> Discord<'_, E> {
     pub(crate) unsafe fn voice_manager(&self) -> *mut sys::IDiscordVoiceManager {
         (*self.inner().core).get_voice_manager.unwrap()(self.inner().core)
     }
+
+    /// Raw pointer to the SDK's core, for calling parts of the SDK that are not yet wrapped
+    /// by this crate. The types are defined in the `discord_game_sdk_sys` crate.
+    ///
+    /// The `raw_*_manager` methods return the raw pointer to each manager.
+    ///
+    /// ## Safety
+    ///
+    /// - The pointer is only valid as long as this `Discord` instance is alive
+    /// - `destroy` must not be called, it is called when `Discord` is dropped
+    /// - `run_callbacks` must not be called during SDK callbacks or `EventHandler` methods,
+    ///   use [`run_callbacks`](#method.run_callbacks) which enforces this
+    /// - Event structs must not be replaced, this crate relies on them to call the `EventHandler`
+    /// - Callbacks passed to the SDK must not unwind
+    ///
+    /// ```rust
+    /// # use discord_game_sdk::*;
+    /// # fn example(discord: Discord<'_, ()>) -> Result<()> {
+    /// let core = unsafe { discord.raw_core() };
+    /// # Ok(()) }
+    /// ```
+    pub unsafe fn raw_core(&self) -> *mut sys::IDiscordCore {
+        self.inner().core
+    }
+
+    /// Raw pointer to the SDK's achievement manager.
+    ///
+    /// ## Safety
+    ///
+    /// See [`raw_core`](#method.raw_core).
+    pub unsafe fn raw_achievement_manager(&self) -> *mut sys::IDiscordAchievementManager {
+        self.achievement_manager()
+    }
+
+    /// Raw pointer to the SDK's activity manager.
+    ///
+    /// ## Safety
+    ///
+    /// See [`raw_core`](#method.raw_core).
+    pub unsafe fn raw_activity_manager(&self) -> *mut sys::IDiscordActivityManager {
+        self.activity_manager()
+    }
+
+    /// Raw pointer to the SDK's application manager.
+    ///
+    /// ## Safety
+    ///
+    /// See [`raw_core`](#method.raw_core).
+    pub unsafe fn raw_application_manager(&self) -> *mut sys::IDiscordApplicationManager {
+        self.application_manager()
+    }
+
+    /// Raw pointer to the SDK's image manager.
+    ///
+    /// ## Safety
+    ///
+    /// See [`raw_core`](#method.raw_core).
+    pub unsafe fn raw_image_manager(&self) -> *mut sys::IDiscordImageManager {
+        self.image_manager()
+    }
+
+    /// Raw pointer to the SDK's lobby manager.
+    ///
+    /// ## Safety
+    ///
+    /// See [`raw_core`](#method.raw_core).
+    pub unsafe fn raw_lobby_manager(&self) -> *mut sys::IDiscordLobbyManager {
+        self.lobby_manager()
+    }
+
+    /// Raw pointer to the SDK's network manager.
+    ///
+    /// ## Safety
+    ///
+    /// See [`raw_core`](#method.raw_core).
+    pub unsafe fn raw_network_manager(&self) -> *mut sys::IDiscordNetworkManager {
+        self.network_manager()
+    }
+
+    /// Raw pointer to the SDK's overlay manager.
+    ///
+    /// ## Safety
+    ///
+    /// See [`raw_core`](#method.raw_core).
+    pub unsafe fn raw_overlay_manager(&self) -> *mut sys::IDiscordOverlayManager {
+        self.overlay_manager()
+    }
+
+    /// Raw pointer to the SDK's relationship manager.
+    ///
+    /// ## Safety
+    ///
+    /// See [`raw_core`](#method.raw_core).
+    pub unsafe fn raw_relationship_manager(&self) -> *mut sys::IDiscordRelationshipManager {
+        self.relationship_manager()
+    }
+
+    /// Raw pointer to the SDK's storage manager.
+    ///
+    /// ## Safety
+    ///
+    /// See [`raw_core`](#method.raw_core).
+    pub unsafe fn raw_storage_manager(&self) -> *mut sys::IDiscordStorageManager {
+        self.storage_manager()
+    }
+
+    /// Raw pointer to the SDK's store manager.
+    ///
+    /// ## Safety
+    ///
+    /// See [`raw_core`](#method.raw_core).
+    pub unsafe fn raw_store_manager(&self) -> *mut sys::IDiscordStoreManager {
+        self.store_manager()
+    }
+
+    /// Raw pointer to the SDK's user manager.
+    ///
+    /// ## Safety
+    ///
+    /// See [`raw_core`](#method.raw_core).
+    pub unsafe fn raw_user_manager(&self) -> *mut sys::IDiscordUserManager {
+        self.user_manager()
+    }
+
+    /// Raw pointer to the SDK's voice manager.
+    ///
+    /// ## Safety
+    ///
+    /// See [`raw_core`](#method.raw_core).
+    pub unsafe fn raw_voice_manager(&self) -> *mut sys::IDiscordVoiceManager {
+        self.voice_manager()
+    }
 }
@@ -1,9 +1,11 @@
 use crate::{
-    iter, sys, to_result::ToResult, utils, Discord, Lobby, LobbyID, LobbyMemberTransaction,
-    LobbyTransaction, NetworkChannelID, Reliability, Result, SearchQuery, UserID,
+    iter, sys, to_result::ToResult, utils, ConnectOptions, Discord, Error, Lobby, LobbyID,
+    LobbyMemberTransaction, LobbyTransaction, NetworkChannelID, Reliability, Result, SearchQuery,
+    UserID,
 };
 use std::{
     borrow::Cow,
+    collections::HashMap,
     convert::{TryFrom, TryInto},
     mem::size_of,
 };
@@ -44,10 +46,17 @@ impl<'d, E> Discord<'d, E> {
 
         let (ptr, fun) = self.two_params(
             move |discord, res: sys::EDiscordResult, lobby: *mut sys::DiscordLobby| {
-                callback(
-                    discord,
-                    res.to_result().map(|()| unsafe { &*(lobby as *mut Lobby) }),
-                )
+                let res = res.to_result().map(|()| unsafe { &*(lobby as *mut Lobby) });
+
+                if let Ok(lobby) = res {
+                    discord
+                        .inner()
+                        .connected_lobbies
+                        .borrow_mut()
+                        .insert(lobby.id());
+                }
+
+                callback(discord, res)
             },
         );
 
@@ -92,8 +101,15 @@ impl<'d, E> Discord<'d, E> {
         lobby_id: LobbyID,
         callback: impl 'd + FnOnce(&Discord<'d, E>, Result<()>),
     ) {
-        let (ptr, fun) = self
-            .one_param(move |discord, res: sys::EDiscordResult| callback(discord, res.to_result()));
+        let (ptr, fun) = self.one_param(move |discord, res: sys::EDiscordResult| {
+            let res = res.to_result();
+
+            if res.is_ok() {
+                discord.inner().forget_lobby(lobby_id);
+            }
+
+            callback(discord, res)
+        });
 
         unsafe {
             let mgr = self.lobby_manager();
@@ -124,10 +140,17 @@ impl<'d, E> Discord<'d, E> {
 
         let (ptr, fun) = self.two_params(
             move |discord, res: sys::EDiscordResult, lobby: *mut sys::DiscordLobby| {
-                callback(
-                    discord,
-                    res.to_result().map(|()| unsafe { &*(lobby as *mut Lobby) }),
-                )
+                let res = res.to_result().map(|()| unsafe { &*(lobby as *mut Lobby) });
+
+                if let Ok(lobby) = res {
+                    discord
+                        .inner()
+                        .connected_lobbies
+                        .borrow_mut()
+                        .insert(lobby.id());
+                }
+
+                callback(discord, res)
             },
         );
 
@@ -145,6 +168,61 @@ impl<'d, E> Discord<'d, E> {
         }
     }
 
+    /// Connects the current user to a given lobby, then to its network layer and voice channel
+    /// as requested by `options`.
+    ///
+    /// If connecting to the network or voice fails, the current user remains connected to
+    /// the lobby and `callback` receives the error.
+    ///
+    /// ## Performance
+    ///
+    /// A nul byte will be appended to `secret` if one is not present.
+    ///
+    /// ```rust
+    /// # use discord_game_sdk::*;
+    /// # fn example(discord: Discord<'_, ()>, lobby_id: LobbyID) -> Result<()> {
+    /// discord.connect_lobby_with_options(
+    ///     lobby_id,
+    ///     "secret\0",
+    ///     ConnectOptions::new().voice(false),
+    ///     |discord, lobby| match lobby {
+    ///         Ok(lobby) => println!("connected to lobby {}", lobby.id()),
+    ///         Err(error) => eprintln!("failed to connect to lobby: {}", error),
+    ///     },
+    /// );
+    /// # Ok(()) }
+    /// ```
+    pub fn connect_lobby_with_options<'s>(
+        &self,
+        lobby_id: LobbyID,
+        secret: impl Into<Cow<'s, str>>,
+        options: &ConnectOptions,
+        callback: impl 'd + FnOnce(&Discord<'d, E>, Result<&Lobby>),
+    ) {
+        let options = *options;
+
+        self.connect_lobby(lobby_id, secret, move |discord, res| {
+            let lobby = match res {
+                Ok(lobby) => lobby.clone(),
+                Err(e) => return callback(discord, Err(e)),
+            };
+
+            if options.network {
+                if let Err(e) = discord.connect_lobby_network(lobby.id()) {
+                    return callback(discord, Err(e));
+                }
+            }
+
+            if options.voice {
+                discord.connect_lobby_voice(lobby.id(), move |discord, res| {
+                    callback(discord, res.map(|()| &lobby))
+                })
+            } else {
+                callback(discord, Ok(&lobby))
+            }
+        })
+    }
+
     /// Connects the current user to a lobby using the special activity secret from the lobby
     /// which is a concatenated lobby ID and its secret.
     ///
@@ -166,10 +244,17 @@ impl<'d, E> Discord<'d, E> {
 
         let (ptr, fun) = self.two_params(
             move |discord, res: sys::EDiscordResult, lobby: *mut sys::DiscordLobby| {
-                callback(
-                    discord,
-                    res.to_result().map(|()| unsafe { &*(lobby as *mut Lobby) }),
-                )
+                let res = res.to_result().map(|()| unsafe { &*(lobby as *mut Lobby) });
+
+                if let Ok(lobby) = res {
+                    discord
+                        .inner()
+                        .connected_lobbies
+                        .borrow_mut()
+                        .insert(lobby.id());
+                }
+
+                callback(discord, res)
             },
         );
 
@@ -194,8 +279,15 @@ impl<'d, E> Discord<'d, E> {
         lobby_id: LobbyID,
         callback: impl 'd + FnOnce(&Discord<'d, E>, Result<()>),
     ) {
-        let (ptr, fun) = self
-            .one_param(move |discord, res: sys::EDiscordResult| callback(discord, res.to_result()));
+        let (ptr, fun) = self.one_param(move |discord, res: sys::EDiscordResult| {
+            let res = res.to_result();
+
+            if res.is_ok() {
+                discord.inner().forget_lobby(lobby_id);
+            }
+
+            callback(discord, res)
+        });
 
         unsafe {
             let mgr = self.lobby_manager();
@@ -203,6 +295,59 @@ impl<'d, E> Discord<'d, E> {
         }
     }
 
+    /// Whether the current user is connected to a given lobby.
+    ///
+    /// This is tracked from the lobbies this crate has created or connected to, and is cleared
+    /// when disconnecting from or deleting them. Losing a lobby without doing so, e.g. when the
+    /// network drops, is reported through
+    /// [`EventHandler::on_lobby_delete`](trait.EventHandler.html#method.on_lobby_delete) or
+    /// [`EventHandler::on_member_disconnect`](trait.EventHandler.html#method.on_member_disconnect)
+    /// for the current user, which also clear it.
+    ///
+    /// ```rust
+    /// # use discord_game_sdk::*;
+    /// # fn example(discord: Discord<'_, ()>, lobby_id: LobbyID) -> Result<()> {
+    /// if !discord.is_connected_to_lobby(lobby_id) {
+    ///     // reconnect
+    /// }
+    /// # Ok(()) }
+    /// ```
+    pub fn is_connected_to_lobby(&self, lobby_id: LobbyID) -> bool {
+        self.inner().connected_lobbies.borrow().contains(&lobby_id)
+    }
+
+    /// Returns the IDs of the lobbies the current user is connected to, in ascending order.
+    ///
+    /// See [`is_connected_to_lobby`](#method.is_connected_to_lobby) for how this is tracked.
+    ///
+    /// ```rust
+    /// # use discord_game_sdk::*;
+    /// # fn example(discord: Discord<'_, ()>) -> Result<()> {
+    /// for lobby_id in discord.iter_connected_lobbies() {
+    ///     let lobby = discord.lobby(lobby_id)?;
+    ///     // ...
+    /// }
+    /// # Ok(()) }
+    /// ```
+    pub fn iter_connected_lobbies(
+        &self,
+    ) -> impl Iterator<Item = LobbyID>
+           + DoubleEndedIterator
+           + ExactSizeIterator
+           + std::iter::FusedIterator
+           + std::fmt::Debug {
+        let mut lobby_ids: Vec<_> = self
+            .inner()
+            .connected_lobbies
+            .borrow()
+            .iter()
+            .cloned()
+            .collect();
+
+        lobby_ids.sort();
+        lobby_ids.into_iter()
+    }
+
     /// Gets the lobby object for a given ID.
     ///
     /// [`lobby_search`](#method.lobby_search) must have completed first.
@@ -340,9 +485,26 @@ impl<'d, E> Discord<'d, E> {
         Ok(iter::Collection::new(
             Box::new(move |i| self.ref_copy().lobby_metadata_at(lobby_id, i)),
             self.lobby_metadata_count(lobby_id)?,
+            Box::new(move || self.ref_copy().lobby_metadata_count(lobby_id).unwrap_or(0)),
         ))
     }
 
+    /// Collects all metadata key-value pairs for a given lobby.
+    ///
+    /// ```rust
+    /// # use discord_game_sdk::*;
+    /// # fn example(discord: Discord<'_, ()>, lobby_id: LobbyID) -> Result<()> {
+    /// let metadata = discord.lobby_metadata_map(lobby_id)?;
+    ///
+    /// if metadata.get("map").map(String::as_str) == Some("de_dust2") {
+    ///     // ...
+    /// }
+    /// # Ok(()) }
+    /// ```
+    pub fn lobby_metadata_map(&self, lobby_id: LobbyID) -> Result<HashMap<String, String>> {
+        self.iter_lobby_metadata(lobby_id)?.collect()
+    }
+
     /// Updates lobby member info for a given member of the lobby.
     ///
     /// > [Method in official docs](https://discordapp.com/developers/docs/game-sdk/lobbies#updatemember)
@@ -391,6 +553,22 @@ impl<'d, E> Discord<'d, E> {
         Ok(count.try_into().unwrap())
     }
 
+    /// Whether a lobby has as many members as its capacity allows.
+    ///
+    /// ```rust
+    /// # use discord_game_sdk::*;
+    /// # fn example(discord: Discord<'_, ()>, lobby_id: LobbyID) -> Result<()> {
+    /// if !discord.lobby_is_full(lobby_id)? {
+    ///     // show "Join" button
+    /// }
+    /// # Ok(()) }
+    /// ```
+    pub fn lobby_is_full(&self, lobby_id: LobbyID) -> Result<bool> {
+        let capacity = self.lobby(lobby_id)?.capacity();
+
+        Ok(self.lobby_member_count(lobby_id)? >= capacity)
+    }
+
     /// Returns the user ID of the lobby member at a certain index.
     ///
     /// > [Method in official docs](https://discordapp.com/developers/docs/game-sdk/lobbies#getmemberuserid)
@@ -428,9 +606,44 @@ impl<'d, E> Discord<'d, E> {
         Ok(iter::Collection::new(
             Box::new(move |i| self.ref_copy().lobby_member_id_at(lobby_id, i)),
             self.lobby_member_count(lobby_id)?,
+            Box::new(move || self.ref_copy().lobby_member_count(lobby_id).unwrap_or(0)),
         ))
     }
 
+    /// Returns the user IDs of the members of a lobby along with whether they are speaking.
+    ///
+    /// The speaking state is tracked from
+    /// [`EventHandler::on_speaking`](trait.EventHandler.html#method.on_speaking) events,
+    /// members that have not been reported speaking yet are considered silent.
+    /// The speaking state is forgotten when leaving the lobby or its voice channel.
+    ///
+    /// ```rust
+    /// # use discord_game_sdk::*;
+    /// # fn example(discord: Discord<'_, ()>, lobby_id: LobbyID) -> Result<()> {
+    /// for (user_id, speaking) in discord.lobby_speaking_members(lobby_id)? {
+    ///     if speaking {
+    ///         // highlight `user_id` in voice HUD
+    ///     }
+    /// }
+    /// # Ok(()) }
+    /// ```
+    pub fn lobby_speaking_members(&self, lobby_id: LobbyID) -> Result<Vec<(UserID, bool)>> {
+        let lobby_speaking = self.inner().lobby_speaking.borrow();
+        let members = lobby_speaking.get(&lobby_id);
+
+        self.iter_lobby_member_ids(lobby_id)?
+            .map(|user_id| {
+                let user_id = user_id?;
+                let speaking = members
+                    .and_then(|members| members.get(&user_id))
+                    .cloned()
+                    .unwrap_or(false);
+
+                Ok((user_id, speaking))
+            })
+            .collect()
+    }
+
     /// Returns member metadata value for a given key.
     ///
     /// ## Performance
@@ -549,6 +762,11 @@ impl<'d, E> Discord<'d, E> {
                     .lobby_member_metadata_at(lobby_id, user_id, i)
             }),
             self.lobby_member_metadata_count(lobby_id, user_id)?,
+            Box::new(move || {
+                self.ref_copy()
+                    .lobby_member_metadata_count(lobby_id, user_id)
+                    .unwrap_or(0)
+            }),
         ))
     }
 
@@ -560,7 +778,21 @@ impl<'d, E> Discord<'d, E> {
     /// If you are, you should use
     /// [`send_lobby_network_message`](#method.send_lobby_network_message) instead.
     ///
+    /// `buffer` should not exceed 4 294 967 295 bytes, text should be sent with
+    /// [`send_lobby_text`](#method.send_lobby_text) which checks its length.
+    ///
     /// > [Method in official docs](https://discordapp.com/developers/docs/game-sdk/lobbies#sendlobbymessage)
+    ///
+    /// ```rust
+    /// # use discord_game_sdk::*;
+    /// # fn example(discord: Discord<'_, ()>, lobby_id: LobbyID) -> Result<()> {
+    /// discord.send_lobby_message(lobby_id, "gg", |discord, result| {
+    ///     if let Err(error) = result {
+    ///         eprintln!("failed to send chat message: {}", error);
+    ///     }
+    /// });
+    /// # Ok(()) }
+    /// ```
     pub fn send_lobby_message(
         &self,
         lobby_id: LobbyID,
@@ -590,12 +822,51 @@ impl<'d, E> Discord<'d, E> {
         }
     }
 
+    /// Sends a UTF-8 text message to the lobby on behalf of the current user,
+    /// see [`send_lobby_message`](#method.send_lobby_message).
+    ///
+    /// Received messages can be decoded with
+    /// [`std::str::from_utf8`](https://doc.rust-lang.org/std/str/fn.from_utf8.html).
+    ///
+    /// ## Errors
+    ///
+    /// `callback` is called immediately with
+    /// [`Error::InvalidPayload`](enum.Error.html#variant.InvalidPayload) if `text` is longer
+    /// than 4 294 967 295 bytes, the most the SDK can send, rather than truncating it.
+    ///
+    /// ```rust
+    /// # use discord_game_sdk::*;
+    /// # fn example(discord: Discord<'_, ()>, lobby_id: LobbyID) -> Result<()> {
+    /// discord.send_lobby_text(lobby_id, "gg", |discord, result| {
+    ///     if let Err(error) = result {
+    ///         eprintln!("failed to send chat message: {}", error);
+    ///     }
+    /// });
+    /// # Ok(()) }
+    /// ```
+    pub fn send_lobby_text(
+        &self,
+        lobby_id: LobbyID,
+        text: &str,
+        callback: impl 'd + FnOnce(&Discord<'d, E>, Result<()>),
+    ) {
+        if u32::try_from(text.len()).is_err() {
+            return callback(self, Err(Error::InvalidPayload));
+        }
+
+        self.send_lobby_message(lobby_id, text, callback)
+    }
+
     /// Searches available lobbies based on the search criteria.
     ///
     /// Lobbies that meet the criteria are then globally filtered.
     /// The callback fires when the list of lobbies is stable and ready for iteration.
     /// You do not necessarily need to access the filtered lobbies within the context of the result callback.
     ///
+    /// The SDK can't cancel a search. If another search is started before this one completes,
+    /// `callback` receives [`Error::TransactionAborted`](enum.Error.html#variant.TransactionAborted)
+    /// so that stale results are not used.
+    ///
     /// > [Method in official docs](https://discordapp.com/developers/docs/game-sdk/lobbies#search)
     pub fn lobby_search(
         &self,
@@ -615,8 +886,17 @@ impl<'d, E> Discord<'d, E> {
             return callback(self, Err(e));
         }
 
-        let (ptr, fun) = self
-            .one_param(move |discord, res: sys::EDiscordResult| callback(discord, res.to_result()));
+        let generation = &self.inner().lobby_search_generation;
+        generation.set(generation.get().wrapping_add(1));
+        let generation = generation.get();
+
+        let (ptr, fun) = self.one_param(move |discord, res: sys::EDiscordResult| {
+            if discord.inner().lobby_search_generation.get() != generation {
+                return callback(discord, Err(Error::TransactionAborted));
+            }
+
+            callback(discord, res.to_result())
+        });
 
         unsafe { (*mgr).search.unwrap()(mgr, tx, ptr, fun) }
     }
@@ -676,9 +956,92 @@ impl<'d, E> Discord<'d, E> {
         iter::Collection::new(
             Box::new(move |i| self.ref_copy().lobby_id_at(i)),
             self.lobby_count(),
+            Box::new(move || self.ref_copy().lobby_count()),
+        )
+    }
+
+    /// Returns an `Iterator` over the lobbies found via the lobby search.
+    ///
+    /// [`lobby_search`](#method.lobby_search) must have completed first.
+    ///
+    /// ```rust
+    /// # use discord_game_sdk::*;
+    /// # fn example(discord: Discord<'_, ()>) -> Result<()> {
+    /// for lobby in discord.iter_lobby_search() {
+    ///     let lobby = lobby?;
+    ///     let members = discord.lobby_member_count(lobby.id())?;
+    ///
+    ///     println!("{}: {}/{}", lobby.id(), members, lobby.capacity());
+    /// }
+    /// # Ok(()) }
+    /// ```
+    pub fn iter_lobby_search(
+        &self,
+    ) -> impl '_
+           + Iterator<Item = Result<Lobby>>
+           + DoubleEndedIterator
+           + ExactSizeIterator
+           + std::iter::FusedIterator
+           + std::fmt::Debug {
+        iter::Collection::new(
+            Box::new(move |i| {
+                let discord = self.ref_copy();
+
+                discord
+                    .lobby_id_at(i)
+                    .and_then(|lobby_id| discord.lobby(lobby_id))
+            }),
+            self.lobby_count(),
+            Box::new(move || self.ref_copy().lobby_count()),
         )
     }
 
+    /// Connects to the first lobby matching the search criteria, or creates a new lobby
+    /// if none were found.
+    ///
+    /// Errors from the search, connection or creation are passed to `callback`,
+    /// e.g. [`Error::LobbyFull`](enum.Error.html#variant.LobbyFull) if the lobby filled up
+    /// in the meantime.
+    ///
+    /// [`LobbyTransaction::owner`](struct.LobbyTransaction.html#method.owner) *MUST NOT* be called.
+    ///
+    /// ```rust
+    /// # use discord_game_sdk::*;
+    /// # fn example(discord: Discord<'_, ()>) -> Result<()> {
+    /// discord.quick_match(
+    ///     SearchQuery::new().limit(1),
+    ///     LobbyTransaction::new().capacity(4),
+    ///     |discord, lobby| match lobby {
+    ///         Ok(lobby) => println!("joined lobby {}", lobby.id()),
+    ///         Err(error) => eprintln!("failed to find a lobby: {}", error),
+    ///     },
+    /// );
+    /// # Ok(()) }
+    /// ```
+    pub fn quick_match(
+        &self,
+        search: &SearchQuery,
+        transaction: &LobbyTransaction,
+        callback: impl 'd + FnOnce(&Discord<'d, E>, Result<&Lobby>),
+    ) {
+        let transaction = transaction.clone();
+
+        self.lobby_search(search, move |discord, res| {
+            if let Err(e) = res {
+                return callback(discord, Err(e));
+            }
+
+            if discord.lobby_count() == 0 {
+                return discord.create_lobby(&transaction, callback);
+            }
+
+            match discord.lobby_id_at(0).and_then(|id| discord.lobby(id)) {
+                Ok(lobby) => discord.connect_lobby(lobby.id(), lobby.secret(), callback),
+                Err(e) => callback(discord, Err(e)),
+            }
+        })
+    }
+
     /// Connects to the voice channel of the current lobby.
     ///
     /// When connected to voice, the user can open their Discord overlay to see a list of other users,
@@ -690,8 +1053,15 @@ impl<'d, E> Discord<'d, E> {
         lobby_id: LobbyID,
         callback: impl 'd + FnOnce(&Discord<'d, E>, Result<()>),
     ) {
-        let (ptr, fun) = self
-            .one_param(move |discord, res: sys::EDiscordResult| callback(discord, res.to_result()));
+        let (ptr, fun) = self.one_param(move |discord, res: sys::EDiscordResult| {
+            let res = res.to_result();
+
+            if res.is_ok() {
+                discord.inner().voice_lobbies.borrow_mut().insert(lobby_id);
+            }
+
+            callback(discord, res)
+        });
 
         unsafe {
             let mgr = self.lobby_manager();
@@ -708,8 +1078,15 @@ impl<'d, E> Discord<'d, E> {
         lobby_id: LobbyID,
         callback: impl 'd + FnOnce(&Discord<'d, E>, Result<()>),
     ) {
-        let (ptr, fun) = self
-            .one_param(move |discord, res: sys::EDiscordResult| callback(discord, res.to_result()));
+        let (ptr, fun) = self.one_param(move |discord, res: sys::EDiscordResult| {
+            let res = res.to_result();
+
+            if res.is_ok() {
+                discord.inner().forget_lobby_voice(lobby_id);
+            }
+
+            callback(discord, res)
+        });
 
         unsafe {
             let mgr = self.lobby_manager();
@@ -718,6 +1095,22 @@ impl<'d, E> Discord<'d, E> {
         }
     }
 
+    /// Whether the current user is connected to the voice channel of a given lobby.
+    ///
+    /// This is tracked from the results of [`connect_lobby_voice`](#method.connect_lobby_voice)
+    /// and [`disconnect_lobby_voice`](#method.disconnect_lobby_voice), and is cleared when
+    /// leaving the lobby, see [`is_connected_to_lobby`](#method.is_connected_to_lobby).
+    ///
+    /// ```rust
+    /// # use discord_game_sdk::*;
+    /// # fn example(discord: Discord<'_, ()>, lobby_id: LobbyID) -> Result<()> {
+    /// let mute_button_enabled = discord.is_connected_to_lobby_voice(lobby_id);
+    /// # Ok(()) }
+    /// ```
+    pub fn is_connected_to_lobby_voice(&self, lobby_id: LobbyID) -> bool {
+        self.inner().voice_lobbies.borrow().contains(&lobby_id)
+    }
+
     /// Connects to the networking layer for the given lobby ID.
     ///
     /// Call this when connecting to the lobby.
@@ -800,4 +1193,34 @@ impl<'d, E> Discord<'d, E> {
             .to_result()
         }
     }
+
+    /// Sends a network message to every member of a lobby, except the current user.
+    ///
+    /// Stops at, and returns, the first error encountered.
+    ///
+    /// ```rust
+    /// # use discord_game_sdk::*;
+    /// # fn example(discord: Discord<'_, ()>, lobby_id: LobbyID) -> Result<()> {
+    /// discord.broadcast_lobby_network_message(lobby_id, 0, b"game state")?;
+    /// discord.flush_lobby_network()?;
+    /// # Ok(()) }
+    /// ```
+    pub fn broadcast_lobby_network_message(
+        &self,
+        lobby_id: LobbyID,
+        channel_id: NetworkChannelID,
+        buffer: &[u8],
+    ) -> Result<()> {
+        let current_user_id = self.current_user()?.id();
+
+        for user_id in self.iter_lobby_member_ids(lobby_id)? {
+            let user_id = user_id?;
+
+            if user_id != current_user_id {
+                self.send_lobby_network_message(lobby_id, user_id, channel_id, buffer)?;
+            }
+        }
+
+        Ok(())
+    }
 }
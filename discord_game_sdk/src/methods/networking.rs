@@ -37,8 +37,32 @@ impl<E> Discord<'_, E> {
         }
     }
 
+    /// Whether [`run_callbacks`](#method.run_callbacks) should call
+    /// [`flush_network`](#method.flush_network) after running callbacks, disabled by default.
+    ///
+    /// Messages sent after `run_callbacks` in a frame are only flushed on the next frame,
+    /// `flush_network` can still be called manually to send them sooner.
+    ///
+    /// ```rust
+    /// # use discord_game_sdk::*;
+    /// # fn example(mut discord: Discord<'_, ()>) -> Result<()> {
+    /// discord.set_auto_flush_network(true);
+    ///
+    /// loop {
+    ///     discord.run_callbacks()?;
+    ///     // ...
+    /// }
+    /// # Ok(()) }
+    /// ```
+    pub fn set_auto_flush_network(&mut self, enabled: bool) {
+        self.inner_mut().auto_flush_network = enabled;
+    }
+
     /// Opens a network connection to another Discord user.
     ///
+    /// The SDK does not report whether the connection has been established,
+    /// a common approach is to wait for a first message from the peer before relying on it.
+    ///
     /// ## Performance
     ///
     /// A nul byte will be appended to `route` if one is not present.
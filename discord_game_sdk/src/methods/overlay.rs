@@ -1,6 +1,19 @@
-use crate::{sys, to_result::ToResult, Action, Discord, Result};
+use crate::{sys, to_result::ToResult, Action, Discord, Error, Result};
 use std::borrow::Cow;
 
+fn check_invite_code(code: &str) -> Result<()> {
+    let code = code.trim_end_matches('\0');
+
+    if code.is_empty()
+        || code.len() > 32
+        || !code.chars().all(|c| c.is_ascii_alphanumeric() || c == '-')
+    {
+        return Err(Error::InvalidPayload);
+    }
+
+    Ok(())
+}
+
 /// # Overlay
 ///
 /// The terminology employed by the Game SDK is confusing, this crate employs the terms "opened"
@@ -11,6 +24,9 @@ use std::borrow::Cow;
 /// | Overlay is appearing and has taken focus | unlocked | opened             |
 /// | Overlay is hidden                        | locked   | closed             |
 ///
+/// The SDK cannot open a user's profile in the overlay, the closest alternative is opening
+/// `https://discord.com/users/{user_id}` in the browser.
+///
 /// > [Chapter in official docs](https://discordapp.com/developers/docs/game-sdk/overlay)
 impl<'d, E> Discord<'d, E> {
     /// Check whether the user has the overlay enabled or disabled.
@@ -40,6 +56,27 @@ impl<'d, E> Discord<'d, E> {
         enabled
     }
 
+    /// Check whether the overlay can be shown in-game, e.g. to disable buttons that open
+    /// [`open_invite_overlay`](#method.open_invite_overlay),
+    /// [`open_guild_invite_overlay`](#method.open_guild_invite_overlay) or
+    /// [`open_voice_settings`](#method.open_voice_settings).
+    ///
+    /// The overlay is only supported on Windows, and must be enabled by the user.
+    /// The SDK does not tell its features apart, when the overlay is unavailable
+    /// they all focus the Discord client instead.
+    ///
+    /// ```rust
+    /// # use discord_game_sdk::*;
+    /// # fn example(discord: Discord<'_, ()>) -> Result<()> {
+    /// if discord.overlay_available() {
+    ///     // ...
+    /// }
+    /// # Ok(()) }
+    /// ```
+    pub fn overlay_available(&self) -> bool {
+        cfg!(windows) && self.overlay_enabled()
+    }
+
     /// Whether the overlay is appearing and has taken focus.
     ///
     /// > [Method in official docs](https://discordapp.com/developers/docs/game-sdk/overlay#islocked)
@@ -64,6 +101,24 @@ impl<'d, E> Discord<'d, E> {
         !locked
     }
 
+    /// Whether the overlay is appearing and has taken focus, without querying the SDK.
+    ///
+    /// This is queried once at startup, then tracked from
+    /// [`EventHandler::on_overlay_toggle`](trait.EventHandler.html#method.on_overlay_toggle)
+    /// events. Prefer [`overlay_opened`](#method.overlay_opened) outside of hot code.
+    ///
+    /// ```rust
+    /// # use discord_game_sdk::*;
+    /// # fn example(discord: Discord<'_, ()>) -> Result<()> {
+    /// if !discord.overlay_is_open_cached() {
+    ///     // handle game input
+    /// }
+    /// # Ok(()) }
+    /// ```
+    pub fn overlay_is_open_cached(&self) -> bool {
+        self.inner().overlay_opened.get()
+    }
+
     /// Open or close the overlay.
     ///
     /// > [Method in official docs](https://discordapp.com/developers/docs/game-sdk/overlay#setlocked)
@@ -93,6 +148,34 @@ impl<'d, E> Discord<'d, E> {
         }
     }
 
+    /// Registers a callback to run the next time the overlay is closed.
+    ///
+    /// The `open_*` methods call back as soon as the overlay has opened, this can be combined
+    /// with them to find out when the user has dismissed it.
+    ///
+    /// ```rust
+    /// # use discord_game_sdk::*;
+    /// # fn example(discord: Discord<'_, ()>) -> Result<()> {
+    /// discord.open_voice_settings(|discord, result| {
+    ///     if let Err(error) = result {
+    ///         return eprintln!("failed open voice settings overlay: {}", error);
+    ///     }
+    ///
+    ///     // pause game
+    ///
+    ///     discord.on_next_overlay_close(|discord| {
+    ///         // resume game
+    ///     });
+    /// });
+    /// # Ok(()) }
+    /// ```
+    pub fn on_next_overlay_close(&self, callback: impl 'd + FnOnce(&Discord<'d, E>)) {
+        self.inner()
+            .overlay_closed_callbacks
+            .borrow_mut()
+            .push(Box::new(callback));
+    }
+
     /// Opens the overlay modal for sending game invitations to users, channels, and servers.
     /// If you do not have a valid activity with all the required fields, this call will error.
     ///
@@ -123,6 +206,50 @@ impl<'d, E> Discord<'d, E> {
         }
     }
 
+    /// Opens the overlay modal for sending invitations to join the current activity.
+    ///
+    /// Shorthand for [`open_invite_overlay`](#method.open_invite_overlay) with
+    /// [`Action::Join`](enum.Action.html#variant.Join).
+    ///
+    /// ```rust
+    /// # use discord_game_sdk::*;
+    /// # fn example(discord: Discord<'_, ()>) -> Result<()> {
+    /// discord.open_join_invite_overlay(|discord, result| {
+    ///     if let Err(error) = result {
+    ///         return eprintln!("failed open join invite overlay: {}", error);
+    ///     }
+    /// });
+    /// # Ok(()) }
+    /// ```
+    pub fn open_join_invite_overlay(
+        &self,
+        callback: impl 'd + FnOnce(&Discord<'d, E>, Result<()>),
+    ) {
+        self.open_invite_overlay(Action::Join, callback)
+    }
+
+    /// Opens the overlay modal for sending invitations to spectate the current activity.
+    ///
+    /// Shorthand for [`open_invite_overlay`](#method.open_invite_overlay) with
+    /// [`Action::Spectate`](enum.Action.html#variant.Spectate).
+    ///
+    /// ```rust
+    /// # use discord_game_sdk::*;
+    /// # fn example(discord: Discord<'_, ()>) -> Result<()> {
+    /// discord.open_spectate_invite_overlay(|discord, result| {
+    ///     if let Err(error) = result {
+    ///         return eprintln!("failed open spectate invite overlay: {}", error);
+    ///     }
+    /// });
+    /// # Ok(()) }
+    /// ```
+    pub fn open_spectate_invite_overlay(
+        &self,
+        callback: impl 'd + FnOnce(&Discord<'d, E>, Result<()>),
+    ) {
+        self.open_invite_overlay(Action::Spectate, callback)
+    }
+
     /// Opens the overlay modal for joining a Discord guild, given its invite code
     /// (e.g.: `ABCDEF` in `https://discord.gg/ABCDEF` or `https://discordapp.com/invite/ABCDEF`).
     ///
@@ -165,6 +292,41 @@ impl<'d, E> Discord<'d, E> {
         }
     }
 
+    /// Same as [`open_guild_invite_overlay`](#method.open_guild_invite_overlay), but checks the
+    /// format of `code` before calling into the SDK.
+    ///
+    /// The SDK cannot open the overlay to a specific guild channel, only to an invite.
+    ///
+    /// ## Errors
+    ///
+    /// `callback` immediately receives [`Error::InvalidPayload`](enum.Error.html#variant.InvalidPayload)
+    /// if `code` is empty, longer than 32 bytes, or contains characters other than ASCII letters,
+    /// digits and `-` (e.g. if a full URL was passed instead of its code).
+    ///
+    /// ```rust
+    /// # use discord_game_sdk::*;
+    /// # fn example(discord: Discord<'_, ()>) -> Result<()> {
+    /// discord.open_guild_invite_overlay_checked("discord-gamesdk", |discord, result| {
+    ///     if let Err(error) = result {
+    ///         return eprintln!("failed open guild invite overlay: {}", error);
+    ///     }
+    /// });
+    /// # Ok(()) }
+    /// ```
+    pub fn open_guild_invite_overlay_checked<'s>(
+        &self,
+        code: impl Into<Cow<'s, str>>,
+        callback: impl 'd + FnOnce(&Discord<'d, E>, Result<()>),
+    ) {
+        let code = code.into();
+
+        if let Err(e) = check_invite_code(&code) {
+            return callback(self, Err(e));
+        }
+
+        self.open_guild_invite_overlay(code, callback)
+    }
+
     /// Opens the overlay widget for voice settings for the currently connected application.
     /// These settings are unique to each user within the context of your application.
     /// That means that a user can have different favorite voice settings for each of their games.
@@ -1,10 +1,30 @@
-use crate::{iter, sys, to_result::ToResult, utils, Discord, Relationship, Result, UserID};
+use crate::{
+    iter, sys, to_result::ToResult, utils, Activity, Discord, Relationship, RelationshipKind,
+    Result, Status, UserID,
+};
 use std::convert::TryInto;
 
 /// # Relationships
 ///
 /// > [Chapter in official docs](https://discordapp.com/developers/docs/game-sdk/relationships)
 impl<E> Discord<'_, E> {
+    /// Whether [`EventHandler::on_relationships_refresh`] has fired at least once,
+    /// before that, the list of relationships is empty.
+    ///
+    /// [`EventHandler::on_relationships_refresh`]: trait.EventHandler.html#method.on_relationships_refresh
+    ///
+    /// ```rust
+    /// # use discord_game_sdk::*;
+    /// # fn example(discord: Discord<'_, ()>) -> Result<()> {
+    /// if discord.relationships_ready() {
+    ///     discord.filter_relationships(|relationship| relationship.user().id() != 0);
+    /// }
+    /// # Ok(()) }
+    /// ```
+    pub fn relationships_ready(&self) -> bool {
+        self.inner().relationships_ready.get()
+    }
+
     /// Get the relationship between the current user and a given user by ID.
     ///
     /// > [Method in official docs](https://discordapp.com/developers/docs/game-sdk/relationships#get)
@@ -39,7 +59,10 @@ impl<E> Discord<'_, E> {
     /// # const DISCORD_CLIENT_ID: ClientID = 0;
     /// # fn example(discord: Discord<'_, ()>) -> Result<()> {
     /// discord.filter_relationships(|relationship| {
-    ///     relationship.presence().activity().application_id() == DISCORD_CLIENT_ID
+    ///     relationship
+    ///         .presence()
+    ///         .activity()
+    ///         .map_or(false, |activity| activity.application_id() == DISCORD_CLIENT_ID)
     /// });
     /// # Ok(()) }
     /// ```
@@ -67,6 +90,35 @@ impl<E> Discord<'_, E> {
         }
     }
 
+    /// Filter relationships to friends who are online and playing this application.
+    ///
+    /// [`RelationshipsRefreshed`](event/relationships/struct.Refresh.html)
+    /// must have fired first.
+    ///
+    /// ```rust
+    /// # use discord_game_sdk::*;
+    /// # fn example(discord: Discord<'_, ()>) -> Result<()> {
+    /// discord.filter_online_friends_in_game();
+    ///
+    /// for relationship in discord.iter_relationships()? {
+    ///     println!("{} is playing", relationship?.user());
+    /// }
+    /// # Ok(()) }
+    /// ```
+    pub fn filter_online_friends_in_game(&self) {
+        let client_id = self.client_id();
+
+        self.filter_relationships(|relationship| {
+            relationship.kind() == RelationshipKind::Friend
+                && relationship.presence().status() != Status::Offline
+                && relationship
+                    .presence()
+                    .activity()
+                    .map(Activity::application_id)
+                    == Some(client_id)
+        })
+    }
+
     /// Returns the number of relationships matching the filter.
     ///
     /// [`RelationshipsRefreshed`](event/relationships/struct.Refresh.html)
@@ -135,6 +187,7 @@ impl<E> Discord<'_, E> {
         Ok(iter::Collection::new(
             Box::new(move |i| self.ref_copy().relationship_at(i)),
             self.relationship_count()?,
+            Box::new(move || self.ref_copy().relationship_count().unwrap_or(0)),
         ))
     }
 }
@@ -1,12 +1,104 @@
-use crate::{iter, sys, to_result::ToResult, utils, Discord, FileStat, Result};
+use crate::{
+    iter, storage_key::check_filename, sys, to_result::ToResult, utils, Discord, Error, FileStat,
+    Result, SortKey, StorageError,
+};
 use std::{
     borrow::Cow,
+    collections::HashMap,
     convert::{TryFrom, TryInto},
+    io,
     mem::size_of,
+    path::Path,
+    rc::Rc,
 };
 
+// Prefix of files written by `write_file_compressed`, the last byte is the format version
+#[cfg(feature = "flate2")]
+const COMPRESSED_MAGIC: &[u8] = b"DGS\x01";
+
+// Calls to `run_callbacks` before retrying a rate-limited write, doubled after every attempt
+const RETRY_BACKOFF_TICKS: u64 = 30;
+
+// Device names Windows reserves in every directory, with or without an extension
+const RESERVED_FILENAMES: &[&str] = &[
+    "con", "prn", "aux", "nul", "com1", "com2", "com3", "com4", "com5", "com6", "com7", "com8",
+    "com9", "lpt1", "lpt2", "lpt3", "lpt4", "lpt5", "lpt6", "lpt7", "lpt8", "lpt9",
+];
+
+// Longest file name most file systems accept, longer escaped keys are listed in `EXPORT_MANIFEST`
+const EXPORT_FILENAME_MAX: usize = 255;
+
+// Lists the keys exported under a numbered `~` name, one `name escaped-key` pair per line.
+// `~` is never produced by `escape_filename`, so these names can't clash with escaped keys
+const EXPORT_MANIFEST: &str = "~keys";
+
+// Keys are percent-encoded so that distinct keys get distinct, portable file names:
+// - only lowercase letters, digits, `-`, `_` and inner `.` are kept,
+//   uppercase letters are encoded for case-insensitive file systems
+// - a leading `.` is encoded to avoid `.`, `..` and hidden files,
+//   a trailing `.` because Windows strips it
+// - the first byte of Windows device names (`con`, `nul.txt`...) is encoded
+fn escape_filename(filename: &str) -> String {
+    let mut escaped = String::with_capacity(filename.len());
+
+    let stem = filename.split('.').next().unwrap_or("");
+    let reserved = RESERVED_FILENAMES.contains(&stem);
+
+    for (i, &byte) in filename.as_bytes().iter().enumerate() {
+        match byte {
+            _ if i == 0 && reserved => escaped.push_str(&format!("%{:02X}", byte)),
+            b'0'..=b'9' | b'a'..=b'z' | b'-' | b'_' => escaped.push(byte as char),
+            b'.' if i > 0 && i < filename.len() - 1 => escaped.push('.'),
+            _ => escaped.push_str(&format!("%{:02X}", byte)),
+        }
+    }
+
+    escaped
+}
+
+// Only accepts the output of `escape_filename`, so that each key maps to a single file name
+fn unescape_filename(escaped: &str) -> Option<String> {
+    let mut bytes = Vec::with_capacity(escaped.len());
+    let mut iter = escaped.bytes();
+
+    while let Some(byte) = iter.next() {
+        if byte == b'%' {
+            let hex = [iter.next()?, iter.next()?];
+
+            if !hex.iter().all(u8::is_ascii_hexdigit) {
+                return None;
+            }
+
+            bytes.push(u8::from_str_radix(std::str::from_utf8(&hex).ok()?, 16).ok()?);
+        } else {
+            bytes.push(byte);
+        }
+    }
+
+    let filename = String::from_utf8(bytes).ok()?;
+
+    if escape_filename(&filename) != escaped {
+        return None;
+    }
+
+    Some(filename)
+}
+
+fn invalid_export_entry(name: &std::ffi::OsStr) -> io::Error {
+    io::Error::new(
+        io::ErrorKind::InvalidData,
+        format!("{:?} is not an exported storage key", name),
+    )
+}
+
 /// # Storage
 ///
+/// Keys must be shorter than 260 bytes and must not contain nul bytes, except for a trailing one,
+/// otherwise methods fail with [`Error::InvalidPayload`](enum.Error.html#variant.InvalidPayload).
+///
+/// Keys can be validated ahead of time with [`StorageKey`](struct.StorageKey.html), methods
+/// that take a single key accept it by reference and never copy it to append a nul byte.
+///
 /// > [Chapter in official docs](https://discordapp.com/developers/docs/game-sdk/storage)
 impl<'d, E> Discord<'d, E> {
     /// Reads data synchronously from the game's allocated save file into a buffer.
@@ -18,7 +110,7 @@ impl<'d, E> Discord<'d, E> {
     ///
     /// ## Performance
     ///
-    /// A nul byte will be appended to `filename` if one is not present.
+    /// If `filename` is not nul-terminated, it is copied to a reused buffer to append a nul byte.
     ///
     /// > [Method in official docs](https://discordapp.com/developers/docs/game-sdk/storage#read)
     ///
@@ -36,11 +128,8 @@ impl<'d, E> Discord<'d, E> {
         filename: impl Into<Cow<'s, str>>,
         mut buffer: impl AsMut<[u8]>,
     ) -> Result<u64> {
-        let mut filename = filename.into();
-
-        if !filename.ends_with('\0') {
-            filename.to_mut().push('\0')
-        }
+        let filename = filename.into();
+        check_filename(&filename)?;
 
         let mut read = 0;
 
@@ -48,24 +137,140 @@ impl<'d, E> Discord<'d, E> {
 
         debug_assert!(u32::try_from(buffer.len()).is_ok());
 
-        unsafe {
+        utils::with_nul_terminated(&filename, |filename| unsafe {
             let mgr = self.storage_manager();
 
             (*mgr).read.unwrap()(
                 mgr,
-                filename.as_ptr(),
+                filename,
                 buffer.as_mut_ptr(),
                 // XXX: u32 should be u64
                 buffer.len().try_into().unwrap_or(u32::max_value()),
                 &mut read,
             )
-            .to_result()?;
-        }
+            .to_result()
+        })?;
 
         // XXX: u32 should be u64
         Ok(read.try_into().unwrap())
     }
 
+    /// Reads data synchronously from the game's allocated save file into a `Vec`,
+    /// reusing its allocation.
+    ///
+    /// `buffer` is cleared and resized to the size of the file, not appended to.
+    /// Returns the number of bytes read.
+    ///
+    /// ## Performance
+    ///
+    /// If `filename` is not nul-terminated, it is copied to a reused buffer to append a nul byte.
+    ///
+    /// ```rust
+    /// # use discord_game_sdk::*;
+    /// # fn example(discord: Discord<'_, ()>) -> Result<()> {
+    /// let mut buffer = Vec::new();
+    ///
+    /// for filename in &["profile_1.save\0", "profile_2.save\0"] {
+    ///     discord.read_file_into(*filename, &mut buffer)?;
+    ///     // ...
+    /// }
+    /// # Ok(()) }
+    /// ```
+    pub fn read_file_into<'s>(
+        &self,
+        filename: impl Into<Cow<'s, str>>,
+        buffer: &mut Vec<u8>,
+    ) -> Result<usize> {
+        let filename = filename.into();
+
+        let size = self.file_stat(filename.as_ref())?.size();
+
+        buffer.clear();
+        buffer.resize(size.try_into().unwrap_or(usize::max_value()), 0);
+
+        let read = self.read_file(filename, &mut buffer[..])?;
+
+        buffer.truncate(read.try_into().unwrap_or(usize::max_value()));
+
+        Ok(buffer.len())
+    }
+
+    /// Reads data synchronously from the game's allocated save file into a `Vec`,
+    /// refusing files larger than `max_bytes` before allocating.
+    ///
+    /// Useful when the file may be corrupt or come from an untrusted source.
+    ///
+    /// ## Errors
+    ///
+    /// Returns [`StorageError::TooLarge`](enum.StorageError.html#variant.TooLarge)
+    /// if the stored file is larger than `max_bytes`, errors from reading the file are returned
+    /// as [`StorageError::Sdk`](enum.StorageError.html#variant.Sdk).
+    ///
+    /// ## Performance
+    ///
+    /// If `filename` is not nul-terminated, it is copied to a reused buffer to append a nul byte.
+    ///
+    /// ```rust
+    /// # use discord_game_sdk::*;
+    /// # fn example(discord: Discord<'_, ()>) -> std::result::Result<(), StorageError> {
+    /// let contents = discord.read_file_to_vec_capped("profile_1.save\0", 1024 * 1024)?;
+    /// # Ok(()) }
+    /// ```
+    pub fn read_file_to_vec_capped<'s>(
+        &self,
+        filename: impl Into<Cow<'s, str>>,
+        max_bytes: u64,
+    ) -> std::result::Result<Vec<u8>, StorageError> {
+        let filename = filename.into();
+
+        let size = self.file_stat(filename.as_ref())?.size();
+
+        if size > max_bytes {
+            return Err(StorageError::TooLarge {
+                size,
+                max: max_bytes,
+            });
+        }
+
+        let mut buffer = vec![0; size.try_into().unwrap_or(usize::max_value())];
+
+        let read = self.read_file(filename, &mut buffer[..])?;
+
+        buffer.truncate(read.try_into().unwrap_or(usize::max_value()));
+
+        Ok(buffer)
+    }
+
+    /// Reads data synchronously from the game's allocated save file into a `String`.
+    ///
+    /// Binary data should be read with [`read_file_into`](#method.read_file_into) instead.
+    ///
+    /// ## Errors
+    ///
+    /// Returns [`StorageError::InvalidUtf8`](enum.StorageError.html#variant.InvalidUtf8)
+    /// if the data is not valid UTF-8, errors from reading the file are returned as
+    /// [`StorageError::Sdk`](enum.StorageError.html#variant.Sdk).
+    ///
+    /// ## Performance
+    ///
+    /// If `filename` is not nul-terminated, it is copied to a reused buffer to append a nul byte.
+    ///
+    /// ```rust
+    /// # use discord_game_sdk::*;
+    /// # fn example(discord: Discord<'_, ()>) -> std::result::Result<(), StorageError> {
+    /// let settings = discord.read_file_to_string("settings.json\0")?;
+    /// # Ok(()) }
+    /// ```
+    pub fn read_file_to_string<'s>(
+        &self,
+        filename: impl Into<Cow<'s, str>>,
+    ) -> std::result::Result<String, StorageError> {
+        let mut buffer = Vec::new();
+        self.read_file_into(filename, &mut buffer)?;
+
+        String::from_utf8(buffer).map_err(StorageError::InvalidUtf8)
+    }
+
     /// Reads data asynchronously from the game's allocated save file into a buffer.
     ///
     /// ## Performance
@@ -92,6 +297,10 @@ impl<'d, E> Discord<'d, E> {
     ) {
         let mut filename = filename.into();
 
+        if let Err(e) = check_filename(&filename) {
+            return callback(self, Err(e));
+        }
+
         if !filename.ends_with('\0') {
             filename.to_mut().push('\0')
         }
@@ -116,6 +325,15 @@ impl<'d, E> Discord<'d, E> {
     /// Reads data asynchronously from the game's allocated save file into a buffer,
     /// starting at a given offset and up to a given length.
     ///
+    /// The file is stat'd first, `length` is clamped to the bytes remaining after `offset`.
+    ///
+    /// ## Errors
+    ///
+    /// `callback` is called immediately with
+    /// [`StorageError::OffsetOutOfRange`](enum.StorageError.html#variant.OffsetOutOfRange)
+    /// if `offset` is past the end of the file. Errors from stat'ing or reading the file are
+    /// returned as [`StorageError::Sdk`](enum.StorageError.html#variant.Sdk).
+    ///
     /// ## Performance
     ///
     /// A nul byte will be appended to `filename` if one is not present.
@@ -138,14 +356,45 @@ impl<'d, E> Discord<'d, E> {
         filename: impl Into<Cow<'s, str>>,
         offset: u64,
         length: u64,
-        callback: impl 'd + FnOnce(&Discord<'d, E>, Result<&[u8]>),
+        callback: impl 'd + FnOnce(&Discord<'d, E>, std::result::Result<&[u8], StorageError>),
     ) {
         let mut filename = filename.into();
 
+        if let Err(e) = check_filename(&filename) {
+            return callback(self, Err(e.into()));
+        }
+
         if !filename.ends_with('\0') {
             filename.to_mut().push('\0')
         }
 
+        let size = match self.file_stat(filename.as_ref()) {
+            Ok(stat) => stat.size(),
+            Err(e) => return callback(self, Err(e.into())),
+        };
+
+        if offset > size {
+            return callback(self, Err(StorageError::OffsetOutOfRange { offset, size }));
+        }
+
+        self.read_file_async_range(
+            &filename,
+            offset,
+            length.min(size - offset),
+            move |discord, res| callback(discord, res.map_err(StorageError::from)),
+        )
+    }
+
+    // `filename` must be nul-terminated
+    fn read_file_async_range(
+        &self,
+        filename: &str,
+        offset: u64,
+        length: u64,
+        callback: impl 'd + FnOnce(&Discord<'d, E>, Result<&[u8]>),
+    ) {
+        debug_assert!(filename.ends_with('\0'));
+
         let (ptr, fun) = self.three_params(
             move |discord, res: sys::EDiscordResult, data: *mut u8, data_len: u32| {
                 callback(
@@ -163,13 +412,55 @@ impl<'d, E> Discord<'d, E> {
         }
     }
 
+    /// Reads data asynchronously from the end of the game's allocated save file,
+    /// up to a given length.
+    ///
+    /// The file is stat'd first, if that fails `callback` is called immediately with the error.
+    ///
+    /// ## Performance
+    ///
+    /// A nul byte will be appended to `filename` if one is not present.
+    ///
+    /// ```rust
+    /// # use discord_game_sdk::*;
+    /// # fn example(discord: Discord<'_, ()>) -> Result<()> {
+    /// discord.read_file_tail_async("profile_1.save\0", 16, |discord, contents| {
+    ///     match contents {
+    ///         Ok(footer) => println!("read {} bytes", footer.len()),
+    ///         Err(error) => eprintln!("failed to read end of file: {}", error),
+    ///     }
+    /// });
+    /// # Ok(()) }
+    /// ```
+    pub fn read_file_tail_async<'s>(
+        &self,
+        filename: impl Into<Cow<'s, str>>,
+        length: u64,
+        callback: impl 'd + FnOnce(&Discord<'d, E>, Result<&[u8]>),
+    ) {
+        let mut filename = filename.into();
+
+        if !filename.ends_with('\0') {
+            filename.to_mut().push('\0')
+        }
+
+        match self.file_stat(filename.as_ref()) {
+            Ok(stat) => {
+                let offset = stat.size().saturating_sub(length);
+
+                self.read_file_async_range(&filename, offset, stat.size() - offset, callback)
+            }
+            Err(e) => callback(self, Err(e)),
+        }
+    }
+
     /// Writes data synchronously to disk, under the given key name.
     ///
     /// `buffer` should not exceed 4 294 967 295 bytes.
     ///
     /// ## Performance
     ///
-    /// A nul byte will be appended to `filename` if one is not present.
+    /// If `filename` is not nul-terminated, it is copied to a reused buffer to append a nul byte.
     ///
     /// > [Method in official docs](https://discordapp.com/developers/docs/game-sdk/storage#write)
     ///
@@ -186,29 +477,108 @@ impl<'d, E> Discord<'d, E> {
         filename: impl Into<Cow<'s, str>>,
         buffer: impl AsRef<[u8]>,
     ) -> Result<()> {
-        let mut filename = filename.into();
-
-        if !filename.ends_with('\0') {
-            filename.to_mut().push('\0')
-        }
+        let filename = filename.into();
+        check_filename(&filename)?;
 
         let buffer = buffer.as_ref();
 
         debug_assert!(u32::try_from(buffer.len()).is_ok());
 
-        unsafe {
+        utils::with_nul_terminated(&filename, |filename| unsafe {
             let mgr = self.storage_manager();
 
             (*mgr).write.unwrap()(
                 mgr,
-                filename.as_ptr(),
+                filename,
                 // XXX: *mut should be *const
                 buffer.as_ptr() as *mut u8,
                 // XXX: u32 should be u64
                 buffer.len().try_into().unwrap_or(u32::max_value()),
             )
             .to_result()
+        })
+    }
+
+    /// Compresses data with DEFLATE, then writes it synchronously to disk under the given key.
+    ///
+    /// The data is prefixed with a header, it must be read with
+    /// [`read_file_compressed`](#method.read_file_compressed).
+    ///
+    /// *Requires the `flate2` feature.*
+    ///
+    /// ## Performance
+    ///
+    /// If `filename` is not nul-terminated, it is copied to a reused buffer to append a nul byte.
+    ///
+    /// ```rust
+    /// # use discord_game_sdk::*;
+    /// # fn example(discord: Discord<'_, ()>) -> Result<()> {
+    /// let contents = r#"{"level": 3, "inventory": []}"#;
+    ///
+    /// discord.write_file_compressed("profile_1.save\0", contents)?;
+    /// # Ok(()) }
+    /// ```
+    #[cfg(feature = "flate2")]
+    pub fn write_file_compressed<'s>(
+        &self,
+        filename: impl Into<Cow<'s, str>>,
+        buffer: impl AsRef<[u8]>,
+    ) -> Result<()> {
+        use std::io::Write;
+
+        let mut encoder =
+            flate2::write::DeflateEncoder::new(COMPRESSED_MAGIC.to_vec(), Default::default());
+
+        // Writing to a `Vec` can't fail
+        encoder.write_all(buffer.as_ref()).unwrap();
+
+        self.write_file(filename, encoder.finish().unwrap())
+    }
+
+    /// Reads data synchronously from the game's allocated save file, then decompresses it.
+    ///
+    /// *Requires the `flate2` feature.*
+    ///
+    /// ## Errors
+    ///
+    /// Returns [`StorageError::NotCompressed`](enum.StorageError.html#variant.NotCompressed) if
+    /// the data was not written by [`write_file_compressed`](#method.write_file_compressed)
+    /// (its header is missing or has another version), or
+    /// [`StorageError::Corrupted`](enum.StorageError.html#variant.Corrupted) if it fails to
+    /// decompress. Errors from reading the file are returned as
+    /// [`StorageError::Sdk`](enum.StorageError.html#variant.Sdk).
+    ///
+    /// ## Performance
+    ///
+    /// If `filename` is not nul-terminated, it is copied to a reused buffer to append a nul byte.
+    ///
+    /// ```rust
+    /// # use discord_game_sdk::*;
+    /// # fn example(discord: Discord<'_, ()>) -> std::result::Result<(), StorageError> {
+    /// let contents = discord.read_file_compressed("profile_1.save\0")?;
+    /// # Ok(()) }
+    /// ```
+    #[cfg(feature = "flate2")]
+    pub fn read_file_compressed<'s>(
+        &self,
+        filename: impl Into<Cow<'s, str>>,
+    ) -> std::result::Result<Vec<u8>, StorageError> {
+        use std::io::Read;
+
+        let mut compressed = Vec::new();
+        self.read_file_into(filename, &mut compressed)?;
+
+        if !compressed.starts_with(COMPRESSED_MAGIC) {
+            return Err(StorageError::NotCompressed);
         }
+
+        let mut buffer = Vec::new();
+
+        flate2::read::DeflateDecoder::new(&compressed[COMPRESSED_MAGIC.len()..])
+            .read_to_end(&mut buffer)
+            .map_err(|_| StorageError::Corrupted)?;
+
+        Ok(buffer)
     }
 
     /// Writes data asynchronously to disk under the given key.
@@ -241,6 +611,10 @@ impl<'d, E> Discord<'d, E> {
     ) {
         let mut filename = filename.into();
 
+        if let Err(e) = check_filename(&filename) {
+            return callback(self, Err(e));
+        }
+
         if !filename.ends_with('\0') {
             filename.to_mut().push('\0')
         }
@@ -268,11 +642,81 @@ impl<'d, E> Discord<'d, E> {
         }
     }
 
+    /// Writes data asynchronously to disk under the given key, writing again if the SDK
+    /// reports [`Error::RateLimited`](enum.Error.html#variant.RateLimited).
+    ///
+    /// New attempts are delayed by 30 calls to [`run_callbacks`](#method.run_callbacks)
+    /// (half a second at 60 calls per second), doubling after every attempt.
+    /// After `max_attempts` attempts, `callback` receives the last error.
+    ///
+    /// ## Performance
+    ///
+    /// `filename` and `buffer` are copied so they can be written again.
+    ///
+    /// ```rust
+    /// # use discord_game_sdk::*;
+    /// # fn example(discord: Discord<'_, ()>) -> Result<()> {
+    /// let contents = "important save data".as_bytes();
+    ///
+    /// discord.write_file_async_retry("autosave\0", contents, 3, |discord, result| {
+    ///     if let Err(error) = result {
+    ///         eprintln!("failed to autosave: {}", error);
+    ///     }
+    /// });
+    /// # Ok(()) }
+    /// ```
+    pub fn write_file_async_retry<'s>(
+        &self,
+        filename: impl Into<Cow<'s, str>>,
+        buffer: impl AsRef<[u8]>,
+        max_attempts: u32,
+        callback: impl 'd + FnOnce(&Discord<'d, E>, Result<()>),
+    ) {
+        self.write_file_async_attempts(
+            filename.into().into_owned(),
+            Rc::from(buffer.as_ref()),
+            1,
+            max_attempts,
+            callback,
+        )
+    }
+
+    fn write_file_async_attempts(
+        &self,
+        filename: String,
+        buffer: Rc<[u8]>,
+        attempt: u32,
+        max_attempts: u32,
+        callback: impl 'd + FnOnce(&Discord<'d, E>, Result<()>),
+    ) {
+        self.write_file_async(
+            filename.clone(),
+            buffer.clone(),
+            move |discord, res| match res {
+                Err(Error::RateLimited) if attempt < max_attempts => {
+                    let backoff = RETRY_BACKOFF_TICKS << (attempt - 1).min(8);
+
+                    discord.after_ticks(backoff, move |discord, res| match res {
+                        Ok(()) => discord.write_file_async_attempts(
+                            filename,
+                            buffer,
+                            attempt + 1,
+                            max_attempts,
+                            callback,
+                        ),
+                        Err(e) => callback(discord, Err(e)),
+                    })
+                }
+                res => callback(discord, res),
+            },
+        )
+    }
+
     /// Deletes written data for the given key.
     ///
     /// ## Performance
     ///
-    /// A nul byte will be appended to `filename` if one is not present.
+    /// If `filename` is not nul-terminated, it is copied to a reused buffer to append a nul byte.
     ///
     /// > [Method in official docs](https://discordapp.com/developers/docs/game-sdk/storage#delete)
     ///
@@ -283,24 +727,21 @@ impl<'d, E> Discord<'d, E> {
     /// # Ok(()) }
     /// ```
     pub fn delete_file<'s>(&self, filename: impl Into<Cow<'s, str>>) -> Result<()> {
-        let mut filename = filename.into();
-
-        if !filename.ends_with('\0') {
-            filename.to_mut().push('\0')
-        }
+        let filename = filename.into();
+        check_filename(&filename)?;
 
-        unsafe {
+        utils::with_nul_terminated(&filename, |filename| unsafe {
             let mgr = self.storage_manager();
 
-            (*mgr).delete_.unwrap()(mgr, filename.as_ptr()).to_result()
-        }
+            (*mgr).delete_.unwrap()(mgr, filename).to_result()
+        })
     }
 
     /// Checks if data exists for a given key.
     ///
     /// ## Performance
     ///
-    /// A nul byte will be appended to `filename` if one is not present.
+    /// If `filename` is not nul-terminated, it is copied to a reused buffer to append a nul byte.
     ///
     /// > [Method in official docs](https://discordapp.com/developers/docs/game-sdk/storage#exists)
     ///
@@ -313,28 +754,216 @@ impl<'d, E> Discord<'d, E> {
     /// # Ok(()) }
     /// ```
     pub fn file_exists<'s>(&self, filename: impl Into<Cow<'s, str>>) -> Result<bool> {
-        let mut filename = filename.into();
-
-        if !filename.ends_with('\0') {
-            filename.to_mut().push('\0')
-        }
+        let filename = filename.into();
+        check_filename(&filename)?;
 
         let mut exists = false;
 
-        unsafe {
+        utils::with_nul_terminated(&filename, |filename| unsafe {
             let mgr = self.storage_manager();
 
-            (*mgr).exists.unwrap()(mgr, filename.as_ptr(), &mut exists).to_result()?;
-        }
+            (*mgr).exists.unwrap()(mgr, filename, &mut exists).to_result()
+        })?;
 
         Ok(exists)
     }
 
+    /// Checks if data exists for each of the given keys.
+    ///
+    /// Missing keys are reported as `false`, any other error is returned immediately.
+    ///
+    /// ## Performance
+    ///
+    /// See [`file_exists`](#method.file_exists).
+    ///
+    /// ```rust
+    /// # use discord_game_sdk::*;
+    /// # fn example(discord: Discord<'_, ()>) -> Result<()> {
+    /// let slots = discord.files_exist(&["slot1.save", "slot2.save", "slot3.save"])?;
+    /// # Ok(()) }
+    /// ```
+    pub fn files_exist(&self, filenames: &[&str]) -> Result<Vec<bool>> {
+        filenames
+            .iter()
+            .map(|filename| match self.file_exists(*filename) {
+                Err(Error::NotFound) => Ok(false),
+                result => result,
+            })
+            .collect()
+    }
+
+    /// Moves data from old keys to new keys, e.g. after changing a save naming scheme.
+    ///
+    /// The SDK has no rename operation, each file is read, written under its new key,
+    /// then deleted. A file is only moved if the old key exists and the new one doesn't,
+    /// so this can safely be run every time the game starts.
+    ///
+    /// Returns whether each file was moved, a failure does not prevent other files from moving.
+    ///
+    /// ```rust
+    /// # use discord_game_sdk::*;
+    /// # fn example(discord: Discord<'_, ()>) -> Result<()> {
+    /// let results = discord.migrate_files(&[
+    ///     ("profile_1.save", "slot1/save"),
+    ///     ("profile_2.save", "slot2/save"),
+    /// ]);
+    ///
+    /// for result in results {
+    ///     if let Err(error) = result {
+    ///         eprintln!("failed to migrate save: {}", error);
+    ///     }
+    /// }
+    /// # Ok(()) }
+    /// ```
+    pub fn migrate_files(&self, mapping: &[(&str, &str)]) -> Vec<Result<bool>> {
+        let mut buffer = Vec::new();
+
+        mapping
+            .iter()
+            .map(|&(old, new)| {
+                if self.files_exist(&[old, new])? != [true, false] {
+                    return Ok(false);
+                }
+
+                self.read_file_into(old, &mut buffer)?;
+                self.write_file(new, &buffer)?;
+                self.delete_file(old)?;
+
+                Ok(true)
+            })
+            .collect()
+    }
+
+    /// Copies every file in storage to a local directory, e.g. to inspect a player's saves.
+    ///
+    /// `dir` is created if missing. Files are named after their keys, percent-encoded so that
+    /// keys containing `/`, uppercase letters or names reserved by Windows map to distinct,
+    /// valid file names. Keys longer than 255 bytes once encoded are written to numbered files
+    /// starting with `~`, listed with their keys in a `~keys` file.
+    ///
+    /// They can be restored with [`import_all`](#method.import_all).
+    ///
+    /// SDK errors are converted with `From<Error> for io::Error`, which keeps the
+    /// [`Error`](enum.Error.html) and picks a matching `io::ErrorKind`.
+    ///
+    /// ```rust
+    /// # use discord_game_sdk::*;
+    /// # fn example(discord: Discord<'_, ()>) -> std::io::Result<()> {
+    /// discord.export_all("storage_dump")?;
+    /// # Ok(()) }
+    /// ```
+    pub fn export_all(&self, dir: impl AsRef<Path>) -> io::Result<()> {
+        let dir = dir.as_ref();
+        let mut buffer = Vec::new();
+        let mut manifest = String::new();
+        let mut numbered = 0;
+
+        std::fs::create_dir_all(dir)?;
+
+        for file_stat in self.iter_file_stats() {
+            let file_stat = file_stat?;
+            let escaped = escape_filename(file_stat.filename());
+
+            let name = if escaped.is_empty() || escaped.len() > EXPORT_FILENAME_MAX {
+                let name = format!("~{}", numbered);
+                numbered += 1;
+
+                manifest.push_str(&format!("{} {}\n", name, escaped));
+                name
+            } else {
+                escaped
+            };
+
+            self.read_file_into(file_stat.filename(), &mut buffer)?;
+
+            std::fs::write(dir.join(name), &buffer)?;
+        }
+
+        if !manifest.is_empty() {
+            std::fs::write(dir.join(EXPORT_MANIFEST), manifest)?;
+        }
+
+        Ok(())
+    }
+
+    /// Writes every file of a directory created by [`export_all`](#method.export_all)
+    /// back to storage, overwriting existing keys.
+    ///
+    /// ## Errors
+    ///
+    /// Returns `io::ErrorKind::InvalidData` before writing anything if an entry of `dir` is not
+    /// a file exported by [`export_all`](#method.export_all), e.g. its name is not an encoded key.
+    ///
+    /// SDK errors are converted with `From<Error> for io::Error`, which keeps the
+    /// [`Error`](enum.Error.html) and picks a matching `io::ErrorKind`.
+    ///
+    /// ```rust
+    /// # use discord_game_sdk::*;
+    /// # fn example(discord: Discord<'_, ()>) -> std::io::Result<()> {
+    /// discord.import_all("storage_dump")?;
+    /// # Ok(()) }
+    /// ```
+    pub fn import_all(&self, dir: impl AsRef<Path>) -> io::Result<()> {
+        let dir = dir.as_ref();
+        let mut numbered = HashMap::new();
+
+        match std::fs::read_to_string(dir.join(EXPORT_MANIFEST)) {
+            Ok(manifest) => {
+                for line in manifest.lines() {
+                    let mut parts = line.splitn(2, ' ');
+
+                    let entry = (parts.next(), parts.next().and_then(unescape_filename));
+
+                    match entry {
+                        (Some(name), Some(filename)) => numbered.insert(name.to_string(), filename),
+                        _ => return Err(invalid_export_entry(EXPORT_MANIFEST.as_ref())),
+                    };
+                }
+            }
+            Err(ref e) if e.kind() == io::ErrorKind::NotFound => {}
+            Err(e) => return Err(e),
+        }
+
+        let mut files = Vec::new();
+
+        for entry in std::fs::read_dir(dir)? {
+            let entry = entry?;
+            let name = entry.file_name();
+
+            if name == EXPORT_MANIFEST {
+                continue;
+            }
+
+            let filename = name.to_str().and_then(|name| {
+                if name.starts_with('~') {
+                    numbered.get(name).cloned()
+                } else {
+                    unescape_filename(name)
+                }
+            });
+
+            match filename {
+                Some(filename) if entry.file_type()?.is_file() => {
+                    files.push((filename, entry.path()))
+                }
+                _ => return Err(invalid_export_entry(&name)),
+            }
+        }
+
+        for (filename, path) in files {
+            let contents = std::fs::read(path)?;
+
+            self.write_file(filename, contents)?;
+        }
+
+        Ok(())
+    }
+
     /// Returns file info for the given key.
     ///
     /// ## Performance
     ///
-    /// A nul byte will be appended to `filename` if one is not present.
+    /// If `filename` is not nul-terminated, it is copied to a reused buffer to append a nul byte.
     ///
     /// > [Method in official docs](https://discordapp.com/developers/docs/game-sdk/storage#stat)
     ///
@@ -345,19 +974,16 @@ impl<'d, E> Discord<'d, E> {
     /// # Ok(()) }
     /// ```
     pub fn file_stat<'s>(&self, filename: impl Into<Cow<'s, str>>) -> Result<FileStat> {
-        let mut filename = filename.into();
-
-        if !filename.ends_with('\0') {
-            filename.to_mut().push('\0')
-        }
+        let filename = filename.into();
+        check_filename(&filename)?;
 
         let mut stat = FileStat(sys::DiscordFileStat::default());
 
-        unsafe {
+        utils::with_nul_terminated(&filename, |filename| unsafe {
             let mgr = self.storage_manager();
 
-            (*mgr).stat.unwrap()(mgr, filename.as_ptr(), &mut stat.0).to_result()?;
-        }
+            (*mgr).stat.unwrap()(mgr, filename, &mut stat.0).to_result()
+        })?;
 
         Ok(stat)
     }
@@ -405,6 +1031,10 @@ impl<'d, E> Discord<'d, E> {
 
     /// Returns an `Iterator` over file stats.
     ///
+    /// Files are looked up by index, deleting a file shifts the following ones down,
+    /// so deleting files while iterating forward skips some of them.
+    /// Iterate in reverse with `.rev()`, or collect the file stats first.
+    ///
     /// ```rust
     /// # use discord_game_sdk::*;
     /// # fn example(discord: Discord<'_, ()>) -> Result<()> {
@@ -412,6 +1042,14 @@ impl<'d, E> Discord<'d, E> {
     ///     let file_stat = file_stat?;
     ///     // ...
     /// }
+    ///
+    /// for file_stat in discord.iter_file_stats().rev() {
+    ///     let file_stat = file_stat?;
+    ///
+    ///     if file_stat.filename().ends_with(".tmp") {
+    ///         discord.delete_file(file_stat.filename())?;
+    ///     }
+    /// }
     /// # Ok(()) }
     /// ```
     pub fn iter_file_stats(
@@ -425,12 +1063,95 @@ impl<'d, E> Discord<'d, E> {
         iter::Collection::new(
             Box::new(move |i| self.ref_copy().file_stat_at(i)),
             self.file_stat_count(),
+            Box::new(move || self.ref_copy().file_stat_count()),
         )
     }
 
+    /// Collects all file stats, sorted in ascending order by the given key.
+    ///
+    /// ## Errors
+    ///
+    /// Returns the first error encountered, rather than a partial list.
+    ///
+    /// ```rust
+    /// # use discord_game_sdk::*;
+    /// # fn example(discord: Discord<'_, ()>) -> Result<()> {
+    /// for file_stat in discord.file_stats_sorted(SortKey::Modified)?.iter().rev() {
+    ///     // most recent saves first
+    /// }
+    /// # Ok(()) }
+    /// ```
+    pub fn file_stats_sorted(&self, key: SortKey) -> Result<Vec<FileStat>> {
+        let mut file_stats = self.iter_file_stats().collect::<Result<Vec<_>>>()?;
+
+        match key {
+            SortKey::Name => file_stats.sort_by(|a, b| a.filename().cmp(b.filename())),
+            SortKey::Size => file_stats.sort_by_key(FileStat::size),
+            SortKey::Modified => file_stats.sort_by_key(FileStat::last_modified),
+        }
+
+        Ok(file_stats)
+    }
+
+    /// Collects all file stats into a map keyed by filename, for repeated lookups.
+    ///
+    /// If the SDK reports the same filename more than once, the last one is kept.
+    ///
+    /// ## Errors
+    ///
+    /// Returns the first error encountered, rather than a partial map.
+    ///
+    /// ```rust
+    /// # use discord_game_sdk::*;
+    /// # fn example(discord: Discord<'_, ()>) -> Result<()> {
+    /// let file_stats = discord.file_stats_map()?;
+    ///
+    /// if let Some(file_stat) = file_stats.get("profile_1.save") {
+    ///     println!("save is {} bytes", file_stat.size());
+    /// }
+    /// # Ok(()) }
+    /// ```
+    pub fn file_stats_map(&self) -> Result<HashMap<String, FileStat>> {
+        self.iter_file_stats()
+            .map(|file_stat| {
+                file_stat.map(|file_stat| (file_stat.filename().to_string(), file_stat))
+            })
+            .collect()
+    }
+
+    /// Returns an `Iterator` over the file stats whose filename starts with `prefix`.
+    ///
+    /// Errors are always yielded.
+    ///
+    /// ```rust
+    /// # use discord_game_sdk::*;
+    /// # fn example(discord: Discord<'_, ()>) -> Result<()> {
+    /// for file_stat in discord.iter_file_stats_with_prefix("slot1/") {
+    ///     let file_stat = file_stat?;
+    ///     // ...
+    /// }
+    /// # Ok(()) }
+    /// ```
+    pub fn iter_file_stats_with_prefix<'a>(
+        &'a self,
+        prefix: &'a str,
+    ) -> impl 'a
+           + Iterator<Item = Result<FileStat>>
+           + DoubleEndedIterator
+           + std::iter::FusedIterator
+           + std::fmt::Debug {
+        self.iter_file_stats().filter(move |file_stat| {
+            file_stat
+                .as_ref()
+                .map_or(true, |file_stat| file_stat.filename().starts_with(prefix))
+        })
+    }
+
     /// Returns the path to the folder where files are stored.
     /// It is specific to the application ID, the current branch, and the current user.
     ///
+    /// The path is cached after the first successful call.
+    ///
     /// > [Method in official docs](https://discordapp.com/developers/docs/game-sdk/storage#getpath)
     ///
     /// ```rust
@@ -440,6 +1161,10 @@ impl<'d, E> Discord<'d, E> {
     /// # Ok(()) }
     /// ```
     pub fn folder_path(&self) -> Result<String> {
+        if let Some(path) = &*self.inner().folder_path.borrow() {
+            return Ok(path.clone());
+        }
+
         let mut path: sys::DiscordPath = [0; size_of::<sys::DiscordPath>()];
 
         unsafe {
@@ -448,6 +1173,52 @@ impl<'d, E> Discord<'d, E> {
             (*mgr).get_path.unwrap()(mgr, &mut path).to_result()?;
         }
 
-        Ok(utils::charbuf_to_str(&path).to_string())
+        let path = utils::charbuf_to_str(&path).to_string();
+
+        *self.inner().folder_path.borrow_mut() = Some(path.clone());
+
+        Ok(path)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn escaped_filenames() {
+        for &filename in &[
+            "slot1/save",
+            "Profile 1.save",
+            ".",
+            "..",
+            ".hidden",
+            "100%",
+            "é",
+            "con",
+            "nul.txt",
+            "com1.save",
+            "save.",
+        ] {
+            let escaped = escape_filename(filename);
+
+            assert!(escaped
+                .bytes()
+                .all(|byte| byte.is_ascii_alphanumeric() || b"%-._".contains(&byte)));
+            assert!(!escaped.starts_with('.'));
+            assert!(!escaped.ends_with('.'));
+            assert_eq!(unescape_filename(&escaped), Some(filename.to_string()));
+        }
+
+        assert_eq!(escape_filename("slot1/save"), "slot1%2Fsave");
+        assert_eq!(escape_filename("con"), "%63on");
+        assert_eq!(escape_filename("console"), "console");
+        assert_eq!(escape_filename("save."), "save%2E");
+        assert_eq!(unescape_filename("con"), None);
+        assert_eq!(unescape_filename("save."), None);
+        assert_eq!(unescape_filename("%2"), None);
+        assert_eq!(unescape_filename("%+1"), None);
+        assert_eq!(unescape_filename("slot1%2fsave"), None);
+        assert_eq!(unescape_filename("Slot1"), None);
     }
 }
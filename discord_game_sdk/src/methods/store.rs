@@ -150,6 +150,7 @@ impl<'d, E> Discord<'d, E> {
         iter::Collection::new(
             Box::new(move |i| self.ref_copy().sku_at(i)),
             self.sku_count(),
+            Box::new(move || self.ref_copy().sku_count()),
         )
     }
 
@@ -295,9 +296,41 @@ impl<'d, E> Discord<'d, E> {
         iter::Collection::new(
             Box::new(move |i| self.ref_copy().entitlement_at(i)),
             self.entitlement_count(),
+            Box::new(move || self.ref_copy().entitlement_count()),
         )
     }
 
+    /// Returns an `Iterator` over the entitlements the current user has for a given SKU.
+    ///
+    /// [`fetch_entitlements`](#method.fetch_entitlements) must have completed first.
+    ///
+    /// Errors are always yielded.
+    ///
+    /// ```rust
+    /// # use discord_game_sdk::*;
+    /// # const SKU_ID: Snowflake = 0;
+    /// # fn example(discord: Discord<'_, ()>) -> Result<()> {
+    /// for entitlement in discord.iter_entitlements_for_sku(SKU_ID) {
+    ///     let entitlement = entitlement?;
+    ///     // ..
+    /// }
+    /// # Ok(()) }
+    /// ```
+    pub fn iter_entitlements_for_sku(
+        &self,
+        sku_id: Snowflake,
+    ) -> impl '_
+           + Iterator<Item = Result<Entitlement>>
+           + DoubleEndedIterator
+           + std::iter::FusedIterator
+           + std::fmt::Debug {
+        self.iter_entitlements().filter(move |entitlement| {
+            entitlement
+                .as_ref()
+                .map_or(true, |entitlement| entitlement.sku_id() == sku_id)
+        })
+    }
+
     /// Whether the user is entitled to the given SKU.
     ///
     /// [`fetch_entitlements`](#method.fetch_entitlements) must have completed first.
@@ -329,6 +362,11 @@ impl<'d, E> Discord<'d, E> {
     ///
     /// [`fetch_entitlements`](#method.fetch_entitlements) must have completed first.
     ///
+    /// The SDK does not provide a way to open a SKU's store page without starting a purchase,
+    /// nor to know whether the overlay was shown or the Discord client was focused instead,
+    /// see [`overlay_enabled`](#method.overlay_enabled).
+    /// A cancelled purchase results in [`Error::PurchaseCanceled`](enum.Error.html#variant.PurchaseCanceled).
+    ///
     /// > [Method in official docs](https://discordapp.com/developers/docs/game-sdk/store#startpurchase)
     ///
     /// ```rust
@@ -1,4 +1,5 @@
 use crate::{sys, to_result::ToResult, Discord, PremiumKind, Result, User, UserFlags, UserID};
+use std::{cell::RefCell, rc::Rc};
 
 /// # Users
 ///
@@ -73,6 +74,70 @@ impl<'d, E> Discord<'d, E> {
         }
     }
 
+    /// Get several users by their IDs.
+    ///
+    /// `callback` is called once all users have been fetched,
+    /// with the results in the same order as `user_ids`.
+    ///
+    /// ```rust
+    /// # use discord_game_sdk::*;
+    /// # fn example(discord: Discord<'_, ()>, lobby_id: LobbyID) -> Result<()> {
+    /// let member_ids = discord
+    ///     .iter_lobby_member_ids(lobby_id)?
+    ///     .collect::<Result<Vec<_>>>()?;
+    ///
+    /// discord.users(&member_ids, |discord, users| {
+    ///     for user in users {
+    ///         match user {
+    ///             Ok(user) => println!("{}#{}", user.username(), user.discriminator()),
+    ///             Err(error) => eprintln!("failed to fetch user: {}", error),
+    ///         }
+    ///     }
+    /// });
+    /// # Ok(()) }
+    /// ```
+    pub fn users(
+        &self,
+        user_ids: &[UserID],
+        callback: impl 'd + FnOnce(&Discord<'d, E>, Vec<Result<User>>),
+    ) {
+        if user_ids.is_empty() {
+            return callback(self, Vec::new());
+        }
+
+        let state = Rc::new(RefCell::new((
+            user_ids.len(),
+            vec![None; user_ids.len()],
+            Some(callback),
+        )));
+
+        for (index, &user_id) in user_ids.iter().enumerate() {
+            let state = state.clone();
+
+            self.user(user_id, move |discord, res| {
+                let (users, callback) = {
+                    let (remaining, users, callback) = &mut *state.borrow_mut();
+
+                    users[index] = Some(res.map(|user| User(user.0)));
+                    *remaining -= 1;
+
+                    if *remaining > 0 {
+                        return;
+                    }
+
+                    (
+                        users.drain(..).map(Option::unwrap).collect(),
+                        callback.take(),
+                    )
+                };
+
+                if let Some(callback) = callback {
+                    callback(discord, users);
+                }
+            });
+        }
+    }
+
     /// Get the Premium type for the currently connected user.
     ///
     /// > [Method in official docs](https://discordapp.com/developers/docs/game-sdk/users#getcurrentuserpremiumtype)
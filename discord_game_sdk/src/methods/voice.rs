@@ -28,6 +28,12 @@ impl<'d, E> Discord<'d, E> {
 
     /// Sets a new voice input mode for the user.
     ///
+    /// ## Errors
+    ///
+    /// [`Error::InvalidPayload`](enum.Error.html#variant.InvalidPayload) is returned
+    /// if the input mode is push-to-talk without a shortcut, or with a malformed shortcut
+    /// containing an empty key (e.g. `ctrl + `).
+    ///
     /// > [Method in official docs](https://discordapp.com/developers/docs/game-sdk/discord-voice#setinputmode)
     ///
     /// ```rust
@@ -48,6 +54,10 @@ impl<'d, E> Discord<'d, E> {
         input_mode: InputMode,
         callback: impl 'd + FnOnce(&Discord<'d, E>, Result<()>),
     ) {
+        if let Err(e) = input_mode.validate() {
+            return callback(self, Err(e));
+        }
+
         let (ptr, fun) = self
             .one_param(move |discord, res: sys::EDiscordResult| callback(discord, res.to_result()));
 
@@ -58,6 +68,37 @@ impl<'d, E> Discord<'d, E> {
         }
     }
 
+    /// Whether the current user is transmitting voice, e.g. while holding the push-to-talk key.
+    ///
+    /// The SDK does not expose this directly, it is tracked from
+    /// [`EventHandler::on_speaking`](trait.EventHandler.html#method.on_speaking) events
+    /// concerning the current user, and is only updated while connected to a lobby's voice channel.
+    /// The current user's ID is cached from
+    /// [`EventHandler::on_current_user_update`](trait.EventHandler.html#method.on_current_user_update),
+    /// or fetched with [`current_user`](#method.current_user) until it fires.
+    /// It is reset when leaving the voice channel or the lobby.
+    ///
+    /// ```rust
+    /// # use discord_game_sdk::*;
+    /// # fn example(discord: Discord<'_, ()>) -> Result<()> {
+    /// if discord.is_transmitting() {
+    ///     // draw microphone icon
+    /// }
+    /// # Ok(()) }
+    /// ```
+    pub fn is_transmitting(&self) -> bool {
+        let current_user_id = match self.current_user_id() {
+            Some(current_user_id) => current_user_id,
+            None => return false,
+        };
+
+        self.inner()
+            .lobby_speaking
+            .borrow()
+            .values()
+            .any(|members| members.get(&current_user_id) == Some(&true))
+    }
+
     /// Whether the current user is muted.
     ///
     /// > [Method in official docs](https://discordapp.com/developers/docs/game-sdk/discord-voice#isselfmute)
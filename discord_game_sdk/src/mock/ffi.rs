@@ -1,14 +1,19 @@
-use crate::sys;
-use std::ffi::c_void;
+use super::MockBuilder;
+use crate::{sys, utils};
+use std::{
+    cell::UnsafeCell,
+    collections::{HashMap, VecDeque},
+    ffi::c_void,
+};
 
 const CORE: &sys::IDiscordCore = &sys::IDiscordCore {
     destroy: {
         unsafe extern "C" fn destroy(_: *mut sys::IDiscordCore) {
-            while let Some(closure) = STATE.as_mut().unwrap().queue.pop() {
+            while let Some(closure) = state().as_mut().unwrap().queue.pop_front() {
                 closure()
             }
 
-            drop(STATE.take());
+            drop(state().take());
         }
 
         Some(destroy)
@@ -16,7 +21,7 @@ const CORE: &sys::IDiscordCore = &sys::IDiscordCore {
 
     run_callbacks: {
         unsafe extern "C" fn run_callbacks(_: *mut sys::IDiscordCore) -> sys::EDiscordResult {
-            while let Some(closure) = STATE.as_mut().unwrap().queue.pop() {
+            while let Some(closure) = state().as_mut().unwrap().queue.pop_front() {
                 closure()
             }
 
@@ -36,16 +41,59 @@ const CORE: &sys::IDiscordCore = &sys::IDiscordCore {
         Some(get_achievement_manager)
     },
 
-    set_log_hook: None,
+    set_log_hook: {
+        unsafe extern "C" fn set_log_hook(
+            _: *mut sys::IDiscordCore,
+            _: sys::EDiscordLogLevel,
+            hook_data: *mut c_void,
+            hook: Option<unsafe extern "C" fn(*mut c_void, sys::EDiscordLogLevel, *const u8)>,
+        ) {
+            state().as_mut().unwrap().log_hook = Some((hook_data, hook.unwrap()));
+        }
+
+        Some(set_log_hook)
+    },
     get_application_manager: None,
-    get_user_manager: None,
+    get_user_manager: {
+        unsafe extern "C" fn get_user_manager(
+            _: *mut sys::IDiscordCore,
+        ) -> *mut sys::IDiscordUserManager {
+            USER_MANAGER as *const _ as *mut _
+        }
+
+        Some(get_user_manager)
+    },
     get_image_manager: None,
-    get_activity_manager: None,
+    get_activity_manager: {
+        unsafe extern "C" fn get_activity_manager(
+            _: *mut sys::IDiscordCore,
+        ) -> *mut sys::IDiscordActivityManager {
+            ACTIVITY_MANAGER as *const _ as *mut _
+        }
+
+        Some(get_activity_manager)
+    },
     get_relationship_manager: None,
-    get_lobby_manager: None,
+    get_lobby_manager: {
+        unsafe extern "C" fn get_lobby_manager(
+            _: *mut sys::IDiscordCore,
+        ) -> *mut sys::IDiscordLobbyManager {
+            LOBBY_MANAGER as *const _ as *mut _
+        }
+
+        Some(get_lobby_manager)
+    },
     get_network_manager: None,
     get_overlay_manager: None,
-    get_storage_manager: None,
+    get_storage_manager: {
+        unsafe extern "C" fn get_storage_manager(
+            _: *mut sys::IDiscordCore,
+        ) -> *mut sys::IDiscordStorageManager {
+            STORAGE_MANAGER as *const _ as *mut _
+        }
+
+        Some(get_storage_manager)
+    },
     get_store_manager: None,
     get_voice_manager: None,
 };
@@ -59,8 +107,8 @@ const ACHIEVEMENT_MANAGER: &sys::IDiscordAchievementManager = &sys::IDiscordAchi
             callback_data: *mut c_void,
             callback: Option<unsafe extern "C" fn(*mut c_void, sys::EDiscordResult)>,
         ) {
-            STATE.as_mut().unwrap().queue.push(Box::new(move || {
-                let state = STATE.as_mut().unwrap();
+            state().as_mut().unwrap().queue.push_back(Box::new(move || {
+                let state = state().as_mut().unwrap();
 
                 for achievement in state.achievements.iter_mut() {
                     if achievement.achievement_id == achievement_id {
@@ -87,7 +135,7 @@ const ACHIEVEMENT_MANAGER: &sys::IDiscordAchievementManager = &sys::IDiscordAchi
             callback_data: *mut c_void,
             callback: Option<unsafe extern "C" fn(*mut c_void, sys::EDiscordResult)>,
         ) {
-            STATE.as_mut().unwrap().queue.push(Box::new(move || {
+            state().as_mut().unwrap().queue.push_back(Box::new(move || {
                 callback.unwrap()(callback_data, sys::DiscordResult_Ok);
             }))
         }
@@ -101,7 +149,7 @@ const ACHIEVEMENT_MANAGER: &sys::IDiscordAchievementManager = &sys::IDiscordAchi
             user_achievement_id: sys::DiscordSnowflake,
             user_achievement: *mut sys::DiscordUserAchievement,
         ) -> sys::EDiscordResult {
-            for achievement in &STATE.as_ref().unwrap().achievements {
+            for achievement in &state().as_ref().unwrap().achievements {
                 if achievement.achievement_id == user_achievement_id {
                     *user_achievement = *achievement;
 
@@ -120,7 +168,7 @@ const ACHIEVEMENT_MANAGER: &sys::IDiscordAchievementManager = &sys::IDiscordAchi
             _: *mut sys::IDiscordAchievementManager,
             count: *mut i32,
         ) {
-            *count = STATE.as_ref().unwrap().achievements.len() as i32;
+            *count = state().as_ref().unwrap().achievements.len() as i32;
         }
         Some(count_user_achievements)
     },
@@ -131,7 +179,7 @@ const ACHIEVEMENT_MANAGER: &sys::IDiscordAchievementManager = &sys::IDiscordAchi
             index: i32,
             user_achievement: *mut sys::DiscordUserAchievement,
         ) -> sys::EDiscordResult {
-            *user_achievement = STATE.as_ref().unwrap().achievements[index as usize];
+            *user_achievement = state().as_ref().unwrap().achievements[index as usize];
 
             sys::DiscordResult_Ok
         }
@@ -140,30 +188,550 @@ const ACHIEVEMENT_MANAGER: &sys::IDiscordAchievementManager = &sys::IDiscordAchi
     },
 };
 
+const STORAGE_MANAGER: &sys::IDiscordStorageManager = &sys::IDiscordStorageManager {
+    count: {
+        unsafe extern "C" fn count(_: *mut sys::IDiscordStorageManager, count: *mut i32) {
+            *count = state().as_ref().unwrap().file_stats.len() as i32;
+        }
+
+        Some(count)
+    },
+
+    exists: {
+        unsafe extern "C" fn exists(
+            _: *mut sys::IDiscordStorageManager,
+            name: *const u8,
+            exists: *mut bool,
+        ) -> sys::EDiscordResult {
+            let name = utils::charptr_to_str(name);
+
+            *exists = state()
+                .as_ref()
+                .unwrap()
+                .file_stats
+                .iter()
+                .any(|file_stat| utils::charbuf_to_str(&file_stat.filename) == name);
+
+            sys::DiscordResult_Ok
+        }
+
+        Some(exists)
+    },
+
+    stat: {
+        unsafe extern "C" fn stat(
+            _: *mut sys::IDiscordStorageManager,
+            name: *const u8,
+            stat: *mut sys::DiscordFileStat,
+        ) -> sys::EDiscordResult {
+            let name = utils::charptr_to_str(name);
+
+            for file_stat in &state().as_ref().unwrap().file_stats {
+                if utils::charbuf_to_str(&file_stat.filename) == name {
+                    *stat = *file_stat;
+
+                    return sys::DiscordResult_Ok;
+                }
+            }
+
+            sys::DiscordResult_NotFound
+        }
+
+        Some(stat)
+    },
+
+    stat_at: {
+        unsafe extern "C" fn stat_at(
+            _: *mut sys::IDiscordStorageManager,
+            index: i32,
+            stat: *mut sys::DiscordFileStat,
+        ) -> sys::EDiscordResult {
+            match state().as_ref().unwrap().file_stats.get(index as usize) {
+                Some(file_stat) => {
+                    *stat = *file_stat;
+
+                    sys::DiscordResult_Ok
+                }
+                None => sys::DiscordResult_NotFound,
+            }
+        }
+
+        Some(stat_at)
+    },
+
+    read: {
+        unsafe extern "C" fn read(
+            _: *mut sys::IDiscordStorageManager,
+            name: *const u8,
+            data: *mut u8,
+            data_length: u32,
+            read: *mut u32,
+        ) -> sys::EDiscordResult {
+            let name = utils::charptr_to_str(name);
+
+            match state().as_ref().unwrap().contents.get(name) {
+                Some(contents) => {
+                    let length = contents.len().min(data_length as usize);
+
+                    std::ptr::copy_nonoverlapping(contents.as_ptr(), data, length);
+                    *read = length as u32;
+
+                    sys::DiscordResult_Ok
+                }
+                None => sys::DiscordResult_NotFound,
+            }
+        }
+
+        Some(read)
+    },
+    read_async: None,
+    read_async_partial: None,
+    write: {
+        unsafe extern "C" fn write(
+            _: *mut sys::IDiscordStorageManager,
+            name: *const u8,
+            data: *mut u8,
+            data_length: u32,
+        ) -> sys::EDiscordResult {
+            store_file(
+                state().as_mut().unwrap(),
+                utils::charptr_to_str(name),
+                std::slice::from_raw_parts(data, data_length as usize),
+            );
+
+            sys::DiscordResult_Ok
+        }
+
+        Some(write)
+    },
+    write_async: {
+        unsafe extern "C" fn write_async(
+            _: *mut sys::IDiscordStorageManager,
+            name: *const u8,
+            data: *mut u8,
+            data_length: u32,
+            callback_data: *mut c_void,
+            callback: Option<unsafe extern "C" fn(*mut c_void, sys::EDiscordResult)>,
+        ) {
+            let state = state().as_mut().unwrap();
+            let name = utils::charptr_to_str(name);
+
+            if state.rate_limited_writes > 0 {
+                state.rate_limited_writes -= 1;
+
+                return state.queue.push_back(Box::new(move || {
+                    callback.unwrap()(callback_data, sys::DiscordResult_RateLimited);
+                }));
+            }
+
+            store_file(
+                state,
+                name,
+                std::slice::from_raw_parts(data, data_length as usize),
+            );
+
+            state.queue.push_back(Box::new(move || {
+                callback.unwrap()(callback_data, sys::DiscordResult_Ok);
+            }))
+        }
+
+        Some(write_async)
+    },
+    delete_: {
+        unsafe extern "C" fn delete_(
+            _: *mut sys::IDiscordStorageManager,
+            name: *const u8,
+        ) -> sys::EDiscordResult {
+            let state = state().as_mut().unwrap();
+            let name = utils::charptr_to_str(name);
+            let count = state.file_stats.len();
+
+            state
+                .file_stats
+                .retain(|file_stat| utils::charbuf_to_str(&file_stat.filename) != name);
+            state.contents.remove(name);
+
+            if state.file_stats.len() < count {
+                sys::DiscordResult_Ok
+            } else {
+                sys::DiscordResult_NotFound
+            }
+        }
+
+        Some(delete_)
+    },
+    get_path: None,
+};
+
+const USER_MANAGER: &sys::IDiscordUserManager = &sys::IDiscordUserManager {
+    get_current_user: {
+        unsafe extern "C" fn get_current_user(
+            _: *mut sys::IDiscordUserManager,
+            current_user: *mut sys::DiscordUser,
+        ) -> sys::EDiscordResult {
+            match state().as_ref().unwrap().current_user_id {
+                Some(id) => {
+                    (*current_user).id = id;
+
+                    sys::DiscordResult_Ok
+                }
+                None => sys::DiscordResult_NotFound,
+            }
+        }
+
+        Some(get_current_user)
+    },
+    get_user: None,
+    get_current_user_premium_type: None,
+    current_user_has_flag: None,
+};
+
+const ACTIVITY_MANAGER: &sys::IDiscordActivityManager = &sys::IDiscordActivityManager {
+    register_command: None,
+    register_steam: None,
+    update_activity: {
+        unsafe extern "C" fn update_activity(
+            _: *mut sys::IDiscordActivityManager,
+            activity: *mut sys::DiscordActivity,
+            callback_data: *mut c_void,
+            callback: Option<unsafe extern "C" fn(*mut c_void, sys::EDiscordResult)>,
+        ) {
+            let state = state().as_mut().unwrap();
+
+            state.activity = Some(*activity);
+
+            state.queue.push_back(Box::new(move || {
+                callback.unwrap()(callback_data, sys::DiscordResult_Ok)
+            }))
+        }
+
+        Some(update_activity)
+    },
+    clear_activity: {
+        unsafe extern "C" fn clear_activity(
+            _: *mut sys::IDiscordActivityManager,
+            callback_data: *mut c_void,
+            callback: Option<unsafe extern "C" fn(*mut c_void, sys::EDiscordResult)>,
+        ) {
+            let state = state().as_mut().unwrap();
+
+            state.activity = None;
+
+            state.queue.push_back(Box::new(move || {
+                callback.unwrap()(callback_data, sys::DiscordResult_Ok)
+            }))
+        }
+
+        Some(clear_activity)
+    },
+    send_request_reply: None,
+    send_invite: None,
+    accept_invite: None,
+};
+
+const LOBBY_MANAGER: &sys::IDiscordLobbyManager = &sys::IDiscordLobbyManager {
+    get_lobby_create_transaction: None,
+    get_lobby_update_transaction: None,
+    get_member_update_transaction: None,
+    create_lobby: None,
+    update_lobby: None,
+    delete_lobby: None,
+    connect_lobby: {
+        unsafe extern "C" fn connect_lobby(
+            _: *mut sys::IDiscordLobbyManager,
+            lobby_id: sys::DiscordLobbyId,
+            _: *mut u8,
+            callback_data: *mut c_void,
+            callback: Option<
+                unsafe extern "C" fn(*mut c_void, sys::EDiscordResult, *mut sys::DiscordLobby),
+            >,
+        ) {
+            state().as_mut().unwrap().queue.push_back(Box::new(move || {
+                let mut lobby = sys::DiscordLobby {
+                    id: lobby_id,
+                    ..Default::default()
+                };
+
+                callback.unwrap()(callback_data, sys::DiscordResult_Ok, &mut lobby)
+            }))
+        }
+
+        Some(connect_lobby)
+    },
+    connect_lobby_with_activity_secret: None,
+    disconnect_lobby: {
+        unsafe extern "C" fn disconnect_lobby(
+            _: *mut sys::IDiscordLobbyManager,
+            _: sys::DiscordLobbyId,
+            callback_data: *mut c_void,
+            callback: Option<unsafe extern "C" fn(*mut c_void, sys::EDiscordResult)>,
+        ) {
+            state().as_mut().unwrap().queue.push_back(Box::new(move || {
+                callback.unwrap()(callback_data, sys::DiscordResult_Ok)
+            }))
+        }
+
+        Some(disconnect_lobby)
+    },
+    get_lobby: None,
+    get_lobby_activity_secret: None,
+    get_lobby_metadata_value: None,
+    get_lobby_metadata_key: None,
+    lobby_metadata_count: None,
+    member_count: None,
+    get_member_user_id: None,
+    get_member_user: None,
+    get_member_metadata_value: None,
+    get_member_metadata_key: None,
+    member_metadata_count: None,
+    update_member: None,
+    send_lobby_message: {
+        unsafe extern "C" fn send_lobby_message(
+            _: *mut sys::IDiscordLobbyManager,
+            lobby_id: sys::DiscordLobbyId,
+            data: *mut u8,
+            data_length: u32,
+            callback_data: *mut c_void,
+            callback: Option<unsafe extern "C" fn(*mut c_void, sys::EDiscordResult)>,
+        ) {
+            let state = state().as_mut().unwrap();
+            let data = std::slice::from_raw_parts(data, data_length as usize).to_vec();
+
+            state.sent_lobby_messages.push((lobby_id, data));
+
+            state.queue.push_back(Box::new(move || {
+                callback.unwrap()(callback_data, sys::DiscordResult_Ok)
+            }))
+        }
+
+        Some(send_lobby_message)
+    },
+    get_search_query: {
+        unsafe extern "C" fn get_search_query(
+            _: *mut sys::IDiscordLobbyManager,
+            query: *mut *mut sys::IDiscordLobbySearchQuery,
+        ) -> sys::EDiscordResult {
+            *query = SEARCH_QUERY as *const _ as *mut _;
+
+            sys::DiscordResult_Ok
+        }
+
+        Some(get_search_query)
+    },
+    search: {
+        unsafe extern "C" fn search(
+            _: *mut sys::IDiscordLobbyManager,
+            _: *mut sys::IDiscordLobbySearchQuery,
+            callback_data: *mut c_void,
+            callback: Option<unsafe extern "C" fn(*mut c_void, sys::EDiscordResult)>,
+        ) {
+            state().as_mut().unwrap().queue.push_back(Box::new(move || {
+                callback.unwrap()(callback_data, sys::DiscordResult_Ok)
+            }))
+        }
+
+        Some(search)
+    },
+    lobby_count: None,
+    get_lobby_id: None,
+    connect_voice: None,
+    disconnect_voice: None,
+    connect_network: None,
+    disconnect_network: None,
+    flush_network: None,
+    open_network_channel: None,
+    send_network_message: None,
+};
+
+// Filters are not mocked, searches must use an empty query
+const SEARCH_QUERY: &sys::IDiscordLobbySearchQuery = &sys::IDiscordLobbySearchQuery {
+    filter: None,
+    sort: None,
+    limit: None,
+    distance: None,
+};
+
+// Takes the state rather than calling `state()`, so callers can keep using theirs
+fn store_file(state: &mut State, name: &str, data: &[u8]) {
+    let position = state
+        .file_stats
+        .iter()
+        .position(|file_stat| utils::charbuf_to_str(&file_stat.filename) == name);
+
+    let file_stat = match position {
+        Some(i) => &mut state.file_stats[i],
+        None => {
+            state.file_stats.push(sys::DiscordFileStat::default());
+            let file_stat = state.file_stats.last_mut().unwrap();
+            utils::write_charbuf(&mut file_stat.filename, name);
+            file_stat
+        }
+    };
+
+    file_stat.size = data.len() as u64;
+
+    state.contents.insert(name.to_string(), data.to_vec());
+}
+
+pub(crate) unsafe fn activity() -> Option<sys::DiscordActivity> {
+    state().as_ref().unwrap().activity
+}
+
+pub(crate) unsafe fn sent_lobby_messages() -> Vec<(sys::DiscordLobbyId, Vec<u8>)> {
+    state().as_ref().unwrap().sent_lobby_messages.clone()
+}
+
+pub(crate) unsafe fn fire_lobby_message(
+    lobby_id: sys::DiscordLobbyId,
+    user_id: sys::DiscordUserId,
+    mut data: Vec<u8>,
+) {
+    state().as_mut().unwrap().queue.push_back(Box::new(move || {
+        let params = &state().as_ref().unwrap().params;
+
+        (*params.lobby_events).on_lobby_message.unwrap()(
+            params.event_data,
+            lobby_id,
+            user_id,
+            data.as_mut_ptr(),
+            data.len() as u32,
+        )
+    }))
+}
+
+pub(crate) unsafe fn fire_speaking(
+    lobby_id: sys::DiscordLobbyId,
+    user_id: sys::DiscordUserId,
+    speaking: bool,
+) {
+    state().as_mut().unwrap().queue.push_back(Box::new(move || {
+        let params = &state().as_ref().unwrap().params;
+
+        (*params.lobby_events).on_speaking.unwrap()(params.event_data, lobby_id, user_id, speaking)
+    }))
+}
+
+pub(crate) unsafe fn fire_member_disconnect(
+    lobby_id: sys::DiscordLobbyId,
+    user_id: sys::DiscordUserId,
+) {
+    state().as_mut().unwrap().queue.push_back(Box::new(move || {
+        let params = &state().as_ref().unwrap().params;
+
+        (*params.lobby_events).on_member_disconnect.unwrap()(params.event_data, lobby_id, user_id)
+    }))
+}
+
+pub(crate) unsafe fn fire_lobby_delete(lobby_id: sys::DiscordLobbyId) {
+    state().as_mut().unwrap().queue.push_back(Box::new(move || {
+        let params = &state().as_ref().unwrap().params;
+
+        (*params.lobby_events).on_lobby_delete.unwrap()(params.event_data, lobby_id, 0)
+    }))
+}
+
+pub(crate) unsafe fn fire_lobby_network_message(
+    lobby_id: sys::DiscordLobbyId,
+    user_id: sys::DiscordUserId,
+    channel_id: sys::DiscordNetworkChannelId,
+    mut data: Vec<u8>,
+) {
+    state().as_mut().unwrap().queue.push_back(Box::new(move || {
+        let params = &state().as_ref().unwrap().params;
+
+        (*params.lobby_events).on_network_message.unwrap()(
+            params.event_data,
+            lobby_id,
+            user_id,
+            channel_id,
+            data.as_mut_ptr(),
+            data.len() as u32,
+        )
+    }))
+}
+
+pub(crate) unsafe fn fire_network_message(
+    peer_id: sys::DiscordNetworkPeerId,
+    channel_id: sys::DiscordNetworkChannelId,
+    mut data: Vec<u8>,
+) {
+    state().as_mut().unwrap().queue.push_back(Box::new(move || {
+        let params = &state().as_ref().unwrap().params;
+
+        (*params.network_events).on_message.unwrap()(
+            params.event_data,
+            peer_id,
+            channel_id,
+            data.as_mut_ptr(),
+            data.len() as u32,
+        )
+    }))
+}
+
+pub(crate) unsafe fn fire_relationships_refresh() {
+    state().as_mut().unwrap().queue.push_back(Box::new(move || {
+        let params = &state().as_ref().unwrap().params;
+
+        (*params.relationship_events).on_refresh.unwrap()(params.event_data)
+    }))
+}
+
+pub(crate) unsafe fn fire_overlay_toggle(locked: bool) {
+    state().as_mut().unwrap().queue.push_back(Box::new(move || {
+        let params = &state().as_ref().unwrap().params;
+
+        (*params.overlay_events).on_toggle.unwrap()(params.event_data, locked)
+    }))
+}
+
+pub(crate) unsafe fn fire_log(level: sys::EDiscordLogLevel, message: &'static str) {
+    state().as_mut().unwrap().queue.push_back(Box::new(move || {
+        let (hook_data, hook) = state().as_ref().unwrap().log_hook.unwrap();
+
+        hook(hook_data, level, message.as_ptr())
+    }))
+}
+
 #[derive(Default)]
 struct State {
     params: sys::DiscordCreateParams,
     achievements: Vec<sys::DiscordUserAchievement>,
-    queue: Vec<Box<dyn FnOnce()>>,
+    file_stats: Vec<sys::DiscordFileStat>,
+    current_user_id: Option<sys::DiscordUserId>,
+    activity: Option<sys::DiscordActivity>,
+    sent_lobby_messages: Vec<(sys::DiscordLobbyId, Vec<u8>)>,
+    contents: HashMap<String, Vec<u8>>,
+    rate_limited_writes: u32,
+    queue: VecDeque<Box<dyn FnOnce()>>,
+    log_hook: Option<(
+        *mut c_void,
+        unsafe extern "C" fn(*mut c_void, sys::EDiscordLogLevel, *const u8),
+    )>,
+}
+
+// Tests run in parallel on separate threads, each thread gets its own instance
+thread_local! {
+    static STATE: UnsafeCell<Option<State>> = UnsafeCell::new(None);
 }
 
-static mut STATE: Option<State> = None;
+unsafe fn state() -> &'static mut Option<State> {
+    STATE.with(|state| &mut *state.get())
+}
 
-pub(crate) unsafe fn create_mock(params: sys::DiscordCreateParams) -> *mut sys::IDiscordCore {
-    if STATE.is_some() {
+pub(crate) unsafe fn create_mock(
+    params: sys::DiscordCreateParams,
+    builder: &MockBuilder,
+) -> *mut sys::IDiscordCore {
+    if state().is_some() {
         panic!("can only hold one instance lol");
     }
 
-    STATE = Some(State {
+    *state() = Some(State {
         params,
-        achievements: (0..10)
-            .map(|achievement_id| sys::DiscordUserAchievement {
-                user_id: 0,
-                achievement_id,
-                percent_complete: 0,
-                unlocked_at: [0; 64],
-            })
-            .collect(),
+        achievements: builder.user_achievements.clone(),
+        file_stats: builder.file_stats.clone(),
+        rate_limited_writes: builder.rate_limited_writes,
+        current_user_id: builder.current_user_id,
         ..Default::default()
     });
 
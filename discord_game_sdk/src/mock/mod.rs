@@ -1,13 +1,78 @@
 use crate::{
     discord::{Discord, DiscordInner},
-    events, CreateFlags, EventHandler, UserAchievement,
+    events, sys, utils, Activity, ActivityController, CreateFlags, Error, EventHandler, LobbyID,
+    NetworkChannelID, NetworkPeerID, SearchQuery, SortKey, StorageError, UserAchievement, UserID,
+};
+use std::{
+    cell::{Cell, RefCell, UnsafeCell},
+    collections::{HashMap, HashSet},
+    marker::PhantomData,
 };
-use std::{cell::UnsafeCell, marker::PhantomData};
 
 mod ffi;
 
-impl<E> Discord<'_, E> {
-    pub(crate) fn mock() -> Self
+/// Canned data served by the mocked managers
+#[derive(Clone, Debug)]
+pub(crate) struct MockBuilder {
+    user_achievements: Vec<sys::DiscordUserAchievement>,
+    file_stats: Vec<sys::DiscordFileStat>,
+    rate_limited_writes: u32,
+    current_user_id: Option<UserID>,
+}
+
+impl Default for MockBuilder {
+    fn default() -> Self {
+        Self {
+            user_achievements: (0..10)
+                .map(|achievement_id| sys::DiscordUserAchievement {
+                    user_id: 0,
+                    achievement_id,
+                    percent_complete: 0,
+                    unlocked_at: [0; 64],
+                })
+                .collect(),
+            file_stats: Vec::new(),
+            rate_limited_writes: 0,
+            current_user_id: None,
+        }
+    }
+}
+
+impl MockBuilder {
+    pub(crate) fn user_achievements(
+        &mut self,
+        user_achievements: impl IntoIterator<Item = sys::DiscordUserAchievement>,
+    ) -> &mut Self {
+        self.user_achievements = user_achievements.into_iter().collect();
+        self
+    }
+
+    pub(crate) fn file_stat(&mut self, filename: &str, size: u64, last_modified: u64) -> &mut Self {
+        let mut file_stat = sys::DiscordFileStat {
+            size,
+            last_modified,
+            ..Default::default()
+        };
+
+        utils::write_charbuf(&mut file_stat.filename, filename);
+
+        self.file_stats.push(file_stat);
+        self
+    }
+
+    /// The next `count` asynchronous writes fail with `RateLimited`
+    pub(crate) fn rate_limited_writes(&mut self, count: u32) -> &mut Self {
+        self.rate_limited_writes = count;
+        self
+    }
+
+    /// The user returned by `current_user`, which fails with `NotFound` otherwise
+    pub(crate) fn current_user_id(&mut self, current_user_id: UserID) -> &mut Self {
+        self.current_user_id = Some(current_user_id);
+        self
+    }
+
+    pub(crate) fn build<'d, E>(&self) -> Discord<'d, E>
     where
         E: EventHandler,
     {
@@ -16,6 +81,21 @@ impl<E> Discord<'_, E> {
 
             core: std::ptr::null_mut(),
             client_id: 0,
+            connected: true,
+            auto_flush_network: false,
+            pending_callbacks: Cell::new(0),
+            ticks: Cell::new(0),
+            delayed_callbacks: RefCell::new(Vec::new()),
+            relationships_ready: Cell::new(false),
+            current_user_id: Cell::new(None),
+            overlay_opened: Cell::new(false),
+            lobby_search_generation: Cell::new(0),
+            lobby_speaking: RefCell::new(HashMap::new()),
+            connected_lobbies: RefCell::new(HashSet::new()),
+            voice_lobbies: RefCell::new(HashSet::new()),
+            folder_path: RefCell::new(None),
+            overlay_closed_callbacks: RefCell::new(Vec::new()),
+            log_sink: None,
             event_handler: UnsafeCell::new(None),
 
             achievement_events: events::achievement::<E>(),
@@ -31,12 +111,85 @@ impl<E> Discord<'_, E> {
 
         let params = instance.create_params(CreateFlags::Default.into());
 
-        instance.inner_mut().core = unsafe { ffi::create_mock(params) };
+        instance.inner_mut().core = unsafe { ffi::create_mock(params, self) };
 
         instance
     }
 }
 
+impl<E> Discord<'_, E> {
+    pub(crate) fn mock() -> Self
+    where
+        E: EventHandler,
+    {
+        MockBuilder::default().build()
+    }
+
+    /// The presence last set through the mocked activity manager
+    pub(crate) fn mock_activity(&self) -> Option<Activity> {
+        unsafe { ffi::activity() }.map(Activity)
+    }
+
+    /// The messages sent through the mocked lobby manager
+    pub(crate) fn sent_lobby_messages(&self) -> Vec<(LobbyID, Vec<u8>)> {
+        unsafe { ffi::sent_lobby_messages() }
+    }
+
+    /// Queues a lobby message, delivered during the next `run_callbacks`
+    pub(crate) fn fire_lobby_message(&self, lobby_id: LobbyID, user_id: UserID, data: &[u8]) {
+        unsafe { ffi::fire_lobby_message(lobby_id, user_id, data.to_vec()) }
+    }
+
+    /// Queues a speaking event, delivered during the next `run_callbacks`
+    pub(crate) fn fire_speaking(&self, lobby_id: LobbyID, user_id: UserID, speaking: bool) {
+        unsafe { ffi::fire_speaking(lobby_id, user_id, speaking) }
+    }
+
+    /// Queues a member disconnection, delivered during the next `run_callbacks`
+    pub(crate) fn fire_member_disconnect(&self, lobby_id: LobbyID, user_id: UserID) {
+        unsafe { ffi::fire_member_disconnect(lobby_id, user_id) }
+    }
+
+    /// Queues a lobby deletion, delivered during the next `run_callbacks`
+    pub(crate) fn fire_lobby_delete(&self, lobby_id: LobbyID) {
+        unsafe { ffi::fire_lobby_delete(lobby_id) }
+    }
+
+    /// Queues a lobby network message, delivered during the next `run_callbacks`
+    pub(crate) fn fire_lobby_network_message(
+        &self,
+        lobby_id: LobbyID,
+        user_id: UserID,
+        channel_id: NetworkChannelID,
+        data: &[u8],
+    ) {
+        unsafe { ffi::fire_lobby_network_message(lobby_id, user_id, channel_id, data.to_vec()) }
+    }
+
+    /// Queues a network message, delivered during the next `run_callbacks`
+    pub(crate) fn fire_network_message(
+        &self,
+        peer_id: NetworkPeerID,
+        channel_id: NetworkChannelID,
+        data: &[u8],
+    ) {
+        unsafe { ffi::fire_network_message(peer_id, channel_id, data.to_vec()) }
+    }
+
+    /// Queues a relationships refresh, delivered during the next `run_callbacks`
+    pub(crate) fn fire_relationships_refresh(&self) {
+        unsafe { ffi::fire_relationships_refresh() }
+    }
+
+    pub(crate) fn fire_overlay_toggle(&self, opened: bool) {
+        unsafe { ffi::fire_overlay_toggle(!opened) }
+    }
+
+    pub(crate) fn fire_log(&self, level: sys::EDiscordLogLevel, message: &'static str) {
+        unsafe { ffi::fire_log(level, message) }
+    }
+}
+
 #[test]
 fn miri_tests() {
     struct E;
@@ -99,3 +252,751 @@ fn miri_tests() {
         discord.set_user_achievement(0, 99, |_discord, _res| {});
     });
 }
+
+#[test]
+fn canned_user_achievements() {
+    let discord = MockBuilder::default()
+        .user_achievements((0..3).map(|achievement_id| sys::DiscordUserAchievement {
+            user_id: 1,
+            achievement_id,
+            percent_complete: 50,
+            unlocked_at: [0; 64],
+        }))
+        .build::<()>();
+
+    assert_eq!(discord.user_achievement_count(), 3);
+
+    let achievements = discord
+        .iter_user_achievements()
+        .collect::<crate::Result<Vec<_>>>()
+        .unwrap();
+
+    assert_eq!(achievements.len(), 3);
+    assert!(achievements.iter().all(|a| a.percent_complete() == 50));
+    assert_eq!(achievements[2].achievement_id(), 2);
+    assert_eq!(discord.user_achievement(1).unwrap().user_id(), 1);
+}
+
+#[test]
+fn canned_file_stats() {
+    let discord = MockBuilder::default()
+        .file_stat("slot1/save", 16, 1)
+        .file_stat("slot1/thumbnail", 1024, 2)
+        .file_stat("slot2/save", 32, 3)
+        .build::<()>();
+
+    assert_eq!(discord.file_stat_count(), 3);
+    assert_eq!(discord.file_stat("slot2/save").unwrap().size(), 32);
+    assert!(discord.file_stat("slot3/save").is_err());
+
+    let filenames = discord
+        .iter_file_stats_with_prefix("slot1/")
+        .map(|file_stat| file_stat.unwrap().filename().to_string())
+        .collect::<Vec<_>>();
+
+    assert_eq!(filenames, vec!["slot1/save", "slot1/thumbnail"]);
+
+    let by_size = discord
+        .file_stats_sorted(SortKey::Size)
+        .unwrap()
+        .iter()
+        .map(|file_stat| file_stat.size())
+        .collect::<Vec<_>>();
+
+    assert_eq!(by_size, vec![16, 32, 1024]);
+
+    let by_name = discord.file_stats_map().unwrap();
+
+    assert_eq!(by_name.len(), 3);
+    assert_eq!(by_name["slot1/thumbnail"].last_modified(), 2);
+
+    assert_eq!(
+        discord
+            .files_exist(&["slot1/save", "slot2/thumbnail", "slot2/save\0"])
+            .unwrap(),
+        vec![true, false, true]
+    );
+}
+
+#[test]
+fn invalid_filenames() {
+    let discord = MockBuilder::default()
+        .file_stat("slot1/save", 16, 1)
+        .build::<()>();
+
+    assert_eq!(
+        discord.file_stat("slot1/save\0.bak").unwrap_err(),
+        Error::InvalidPayload
+    );
+    assert_eq!(
+        discord.file_stat("a".repeat(260)).unwrap_err(),
+        Error::InvalidPayload
+    );
+    assert!(discord.file_stat("slot1/save\0").is_ok());
+
+    let result = std::rc::Rc::new(Cell::new(None));
+
+    {
+        let result = result.clone();
+
+        discord.write_file_async("slot1/save\0.bak", b"data", move |_discord, res| {
+            result.set(Some(res));
+        });
+    }
+
+    assert_eq!(result.get(), Some(Err(Error::InvalidPayload)));
+}
+
+#[test]
+fn invalid_invite_codes() {
+    let discord = MockBuilder::default().build::<()>();
+
+    for code in &["", "https://discord.gg/ABCDEF", "ABC DEF", &"a".repeat(33)] {
+        let result = std::rc::Rc::new(Cell::new(None));
+
+        {
+            let result = result.clone();
+
+            discord.open_guild_invite_overlay_checked(*code, move |_discord, res| {
+                result.set(Some(res));
+            });
+        }
+
+        assert_eq!(result.get(), Some(Err(Error::InvalidPayload)));
+    }
+}
+
+#[test]
+fn capped_read_too_large() {
+    let discord = MockBuilder::default()
+        .file_stat("slot1/save", 64, 1)
+        .build::<()>();
+
+    assert_eq!(
+        discord.read_file_to_vec_capped("slot1/save", 16),
+        Err(StorageError::TooLarge { size: 64, max: 16 })
+    );
+    assert_eq!(
+        discord.read_file_to_vec_capped("slot1\0save", 16),
+        Err(StorageError::Sdk(Error::InvalidPayload))
+    );
+}
+
+#[test]
+fn deleted_file_contents() {
+    let discord = Discord::<()>::mock();
+
+    discord.write_file("slot1/save", b"data").unwrap();
+    assert_eq!(
+        discord.read_file_to_vec_capped("slot1/save", 16).unwrap(),
+        b"data"
+    );
+
+    discord.delete_file("slot1/save").unwrap();
+    assert_eq!(
+        discord.read_file("slot1/save", &mut [0; 16]),
+        Err(Error::NotFound)
+    );
+}
+
+#[test]
+fn text_files() {
+    let discord = Discord::<()>::mock();
+
+    discord.write_file("settings.json", "{}").unwrap();
+    assert_eq!(discord.read_file_to_string("settings.json").unwrap(), "{}");
+
+    discord.write_file("settings.json", b"{\xff}").unwrap();
+
+    match discord.read_file_to_string("settings.json") {
+        Err(StorageError::InvalidUtf8(error)) => assert_eq!(error.into_bytes(), b"{\xff}"),
+        res => panic!("unexpected {:?}", res),
+    }
+
+    assert_eq!(
+        discord.read_file_to_string("missing.json"),
+        Err(StorageError::Sdk(Error::NotFound))
+    );
+}
+
+#[test]
+fn rate_limited_write_retries() {
+    let mut discord = MockBuilder::default().rate_limited_writes(2).build::<()>();
+    let result = std::rc::Rc::new(Cell::new(None));
+
+    {
+        let result = result.clone();
+
+        discord.write_file_async_retry("slot1/save", b"data", 3, move |_discord, res| {
+            result.set(Some(res));
+        });
+    }
+
+    let mut ticks = 0;
+
+    while result.get().is_none() {
+        discord.run_callbacks().unwrap();
+        ticks += 1;
+
+        assert!(ticks < 1000);
+    }
+
+    // Backing off for 30 then 60 ticks
+    assert!(ticks > 90, "{}", ticks);
+    assert_eq!(result.get(), Some(Ok(())));
+    assert_eq!(discord.file_stat("slot1/save").unwrap().size(), 4);
+    assert_eq!(discord.inner().pending_callbacks.get(), 0);
+}
+
+#[test]
+fn rate_limited_write_gives_up() {
+    let mut discord = MockBuilder::default().rate_limited_writes(3).build::<()>();
+    let result = std::rc::Rc::new(Cell::new(None));
+
+    {
+        let result = result.clone();
+
+        discord.write_file_async_retry("slot1/save", b"data", 2, move |_discord, res| {
+            result.set(Some(res));
+        });
+    }
+
+    for _ in 0..100 {
+        discord.run_callbacks().unwrap();
+    }
+
+    assert_eq!(result.get(), Some(Err(Error::RateLimited)));
+    assert!(discord.file_stat("slot1/save").is_err());
+}
+
+#[test]
+fn export_import_round_trip() {
+    let dir = std::env::temp_dir().join(format!(
+        "discord_game_sdk_export_{}_{:?}",
+        std::process::id(),
+        std::thread::current().id()
+    ));
+    let _ = std::fs::remove_dir_all(&dir);
+
+    let long = "A".repeat(259);
+    let files: Vec<(&str, &[u8])> = vec![
+        ("slot1/save", b"one"),
+        ("Profile 1.save", b"two"),
+        ("con", b"three"),
+        ("save.", b"four"),
+        (&long, b"five"),
+    ];
+
+    {
+        let discord = Discord::<()>::mock();
+
+        for &(filename, contents) in &files {
+            discord.write_file(filename, contents).unwrap();
+        }
+
+        discord.export_all(&dir).unwrap();
+    }
+
+    let discord = Discord::<()>::mock();
+    discord.import_all(&dir).unwrap();
+
+    assert_eq!(discord.file_stat_count(), files.len() as u32);
+
+    for &(filename, contents) in &files {
+        assert_eq!(
+            discord.read_file_to_vec_capped(filename, 16).unwrap(),
+            contents
+        );
+    }
+
+    // Nothing is written if an entry can't be decoded
+    std::fs::write(dir.join("Slot1"), b"six").unwrap();
+
+    drop(discord);
+    let discord = Discord::<()>::mock();
+
+    let error = discord.import_all(&dir).unwrap_err();
+    assert_eq!(error.kind(), std::io::ErrorKind::InvalidData);
+    assert_eq!(discord.file_stat_count(), 0);
+
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+#[cfg(feature = "flate2")]
+fn compressed_files() {
+    let discord = MockBuilder::default().build::<()>();
+
+    discord
+        .write_file_compressed("slot1/save", b"important save data")
+        .unwrap();
+    assert_eq!(
+        discord.read_file_compressed("slot1/save").unwrap(),
+        b"important save data"
+    );
+
+    // Not written by `write_file_compressed`
+    discord.write_file("slot2/save", b"DGS\x02data").unwrap();
+    assert_eq!(
+        discord.read_file_compressed("slot2/save"),
+        Err(StorageError::NotCompressed)
+    );
+
+    // Corrupted after the header
+    discord
+        .write_file("slot3/save", b"DGS\x01\xff\xff")
+        .unwrap();
+    assert_eq!(
+        discord.read_file_compressed("slot3/save"),
+        Err(StorageError::Corrupted)
+    );
+
+    assert_eq!(
+        discord.read_file_compressed("slot4/save"),
+        Err(StorageError::Sdk(Error::NotFound))
+    );
+}
+
+#[test]
+fn partial_read_past_end() {
+    let discord = MockBuilder::default()
+        .file_stat("slot1/save", 16, 1)
+        .build::<()>();
+
+    let result = std::rc::Rc::new(Cell::new(None));
+
+    {
+        let result = result.clone();
+
+        discord.read_file_async_partial("slot1/save", 17, 4, move |_discord, res| {
+            result.set(Some(res.map(<[u8]>::len)));
+        });
+    }
+
+    assert_eq!(
+        result.take(),
+        Some(Err(StorageError::OffsetOutOfRange {
+            offset: 17,
+            size: 16
+        }))
+    );
+}
+
+#[test]
+fn shrinking_collection() {
+    let discord = MockBuilder::default()
+        .file_stat("slot1/save", 16, 1)
+        .file_stat("slot2/save", 32, 2)
+        .file_stat("slot3/save", 64, 3)
+        .build::<()>();
+
+    // Deleting while iterating forward skips files, but does not yield errors
+    for file_stat in discord.iter_file_stats() {
+        discord.delete_file(file_stat.unwrap().filename()).unwrap();
+    }
+
+    assert_eq!(discord.file_stat_count(), 1);
+
+    {
+        let mut iter = discord.iter_file_stats();
+        assert_eq!(iter.len(), 1);
+        assert!(iter.next().unwrap().is_ok());
+        assert_eq!(iter.len(), 0);
+    }
+
+    drop(discord);
+
+    let discord = MockBuilder::default()
+        .file_stat("slot1/save", 16, 1)
+        .file_stat("slot2/save", 32, 2)
+        .file_stat("slot3/save", 64, 3)
+        .build::<()>();
+
+    let mut deleted = Vec::new();
+
+    for file_stat in discord.iter_file_stats().rev() {
+        let file_stat = file_stat.unwrap();
+
+        discord.delete_file(file_stat.filename()).unwrap();
+        deleted.push(file_stat.filename().to_string());
+    }
+
+    assert_eq!(deleted, vec!["slot3/save", "slot2/save", "slot1/save"]);
+    assert_eq!(discord.file_stat_count(), 0);
+}
+
+#[test]
+fn panicking_callback() {
+    let mut discord = Discord::<()>::mock();
+    let written = std::rc::Rc::new(std::cell::Cell::new(false));
+
+    discord.write_file_async("panic", b"data", |_discord, _res| {
+        panic!("panicking callback");
+    });
+
+    {
+        let written = written.clone();
+
+        discord.write_file_async("after", b"data", move |_discord, res| {
+            written.set(res.is_ok());
+        });
+    }
+
+    assert!(discord
+        .drain_pending(std::time::Duration::from_secs(1))
+        .unwrap());
+
+    assert!(written.get());
+    assert_eq!(discord.file_stat("panic").unwrap().size(), 4);
+}
+
+#[test]
+fn batched_user_achievements() {
+    let mut discord = Discord::<()>::mock();
+    let calls = std::rc::Rc::new(std::cell::Cell::new(0));
+
+    {
+        let calls = calls.clone();
+
+        discord.set_user_achievements(&[(1, 100), (2, 50), (3, 10)], move |_discord, res| {
+            res.unwrap();
+            calls.set(calls.get() + 1);
+        });
+    }
+
+    assert!(discord
+        .drain_pending(std::time::Duration::from_secs(1))
+        .unwrap());
+
+    assert_eq!(calls.get(), 1);
+    assert_eq!(discord.user_achievement(2).unwrap().percent_complete(), 50);
+}
+
+#[test]
+fn relationships_ready() {
+    let mut discord = Discord::<()>::mock();
+
+    assert!(!discord.relationships_ready());
+
+    discord.fire_relationships_refresh();
+    discord.run_callbacks().unwrap();
+
+    assert!(discord.relationships_ready());
+}
+
+#[test]
+fn lobby_speaking() {
+    let mut discord = Discord::<()>::mock();
+    discord.inner().current_user_id.set(Some(10));
+
+    let speaking = |discord: &Discord<'_, ()>, lobby_id| {
+        let mut members = discord
+            .inner()
+            .lobby_speaking
+            .borrow()
+            .get(&lobby_id)
+            .map(|members| members.iter().map(|(&k, &v)| (k, v)).collect::<Vec<_>>())
+            .unwrap_or_default();
+        members.sort();
+        members
+    };
+
+    discord.fire_speaking(1, 10, true);
+    discord.fire_speaking(1, 20, true);
+    discord.fire_speaking(1, 30, true);
+    discord.fire_speaking(1, 30, false);
+    discord.fire_speaking(2, 20, true);
+    discord.run_callbacks().unwrap();
+
+    assert_eq!(
+        speaking(&discord, 1),
+        vec![(10, true), (20, true), (30, false)]
+    );
+    assert!(discord.is_transmitting());
+
+    discord.fire_member_disconnect(1, 20);
+    discord.run_callbacks().unwrap();
+
+    assert_eq!(speaking(&discord, 1), vec![(10, true), (30, false)]);
+    assert!(discord.is_transmitting());
+
+    discord.fire_member_disconnect(1, 10);
+    discord.run_callbacks().unwrap();
+
+    assert_eq!(speaking(&discord, 1), vec![]);
+    assert!(!discord.is_transmitting());
+
+    discord.fire_lobby_delete(2);
+    discord.run_callbacks().unwrap();
+
+    assert_eq!(speaking(&discord, 2), vec![]);
+}
+
+#[test]
+fn superseded_lobby_search() {
+    let mut discord = Discord::<()>::mock();
+    let results = std::rc::Rc::new(RefCell::new(Vec::new()));
+
+    for search in 0..2 {
+        let results = results.clone();
+
+        discord.lobby_search(&SearchQuery::new(), move |_, res| {
+            results.borrow_mut().push((search, res))
+        });
+    }
+
+    discord.run_callbacks().unwrap();
+
+    assert_eq!(
+        *results.borrow(),
+        vec![(0, Err(Error::TransactionAborted)), (1, Ok(()))]
+    );
+
+    // A completed search does not abort the next one
+    {
+        let results = results.clone();
+
+        discord.lobby_search(&SearchQuery::new(), move |_, res| {
+            results.borrow_mut().push((2, res))
+        });
+    }
+
+    discord.run_callbacks().unwrap();
+
+    assert_eq!(results.borrow()[2], (2, Ok(())));
+}
+
+#[test]
+fn activity_controller() {
+    let discord = Discord::<()>::mock();
+    let mut controller = ActivityController::new();
+
+    let state = |discord: &Discord<'_, ()>| {
+        discord
+            .mock_activity()
+            .map(|activity| activity.state().to_string())
+    };
+
+    controller.update(&discord, Activity::empty().with_state("Menu"), |_, _| {});
+    assert_eq!(state(&discord), Some("Menu".to_string()));
+
+    controller.pause(&discord, |_, _| {});
+    assert!(controller.is_paused());
+    assert_eq!(state(&discord), None);
+
+    // Only remembered while paused
+    controller.update(&discord, Activity::empty().with_state("Lobby"), |_, _| {});
+    assert_eq!(controller.activity().unwrap().state(), "Lobby");
+    assert_eq!(state(&discord), None);
+
+    controller.resume(&discord, |_, _| {});
+    assert!(!controller.is_paused());
+    assert_eq!(state(&discord), Some("Lobby".to_string()));
+
+    controller.pause_with_state(&discord, "Paused", |_, _| {});
+    assert_eq!(state(&discord), Some("Paused".to_string()));
+
+    // Keeps the paused state
+    controller.update(
+        &discord,
+        Activity::empty().with_details("In Match"),
+        |_, _| {},
+    );
+    assert_eq!(controller.activity().unwrap().state(), "");
+    assert_eq!(state(&discord), Some("Paused".to_string()));
+    assert_eq!(discord.mock_activity().unwrap().details(), "In Match");
+
+    controller.clear(&discord, |_, _| {});
+    assert_eq!(controller.activity(), None);
+    assert_eq!(state(&discord), None);
+
+    // Nothing to set
+    controller.resume(&discord, |_, _| {});
+    assert_eq!(state(&discord), None);
+
+    controller.pause_with_state(&discord, "Paused", |_, _| {});
+    assert_eq!(state(&discord), None);
+}
+
+#[test]
+fn connected_lobbies() {
+    let mut discord = Discord::<()>::mock();
+    discord.inner().current_user_id.set(Some(10));
+
+    for lobby_id in &[3, 1, 2, 4] {
+        discord.connect_lobby(*lobby_id, "secret", |_, res| assert!(res.is_ok()));
+    }
+
+    discord.run_callbacks().unwrap();
+
+    assert_eq!(discord.iter_connected_lobbies().len(), 4);
+    assert_eq!(
+        discord.iter_connected_lobbies().rev().collect::<Vec<_>>(),
+        vec![4, 3, 2, 1]
+    );
+
+    discord.disconnect_lobby(3, |_, res| assert!(res.is_ok()));
+    discord.fire_lobby_delete(2);
+    discord.fire_member_disconnect(1, 20);
+    discord.fire_member_disconnect(4, 10);
+    discord.run_callbacks().unwrap();
+
+    assert_eq!(
+        discord.iter_connected_lobbies().collect::<Vec<_>>(),
+        vec![1]
+    );
+    assert!(discord.is_connected_to_lobby(1));
+    assert!(!discord.is_connected_to_lobby(4));
+}
+
+#[test]
+fn lobby_text() {
+    let mut discord = Discord::<()>::mock();
+
+    discord.send_lobby_text(1, "gg ✓", |_, res| assert_eq!(res, Ok(())));
+    discord.run_callbacks().unwrap();
+
+    assert_eq!(
+        discord.sent_lobby_messages(),
+        vec![(1, "gg ✓".as_bytes().to_vec())]
+    );
+}
+
+#[test]
+fn current_user_before_update() {
+    let mut discord = MockBuilder::default().current_user_id(10).build::<()>();
+
+    discord.connect_lobby(1, "secret", |_, _| {});
+    discord.fire_speaking(1, 10, true);
+    discord.run_callbacks().unwrap();
+
+    assert!(discord.is_transmitting());
+
+    discord.fire_member_disconnect(1, 10);
+    discord.run_callbacks().unwrap();
+
+    assert!(!discord.is_transmitting());
+    assert!(!discord.is_connected_to_lobby(1));
+}
+
+#[test]
+fn overlay_closed_callbacks() {
+    let mut discord = Discord::<()>::mock();
+    let calls = std::rc::Rc::new(Cell::new(0));
+
+    {
+        let calls = calls.clone();
+        discord.on_next_overlay_close(move |_| calls.set(calls.get() + 1));
+    }
+
+    discord.fire_overlay_toggle(true);
+    discord.run_callbacks().unwrap();
+    assert_eq!(calls.get(), 0);
+    assert!(discord.overlay_is_open_cached());
+
+    discord.fire_overlay_toggle(false);
+    discord.fire_overlay_toggle(false);
+    discord.run_callbacks().unwrap();
+    assert_eq!(calls.get(), 1);
+    assert!(!discord.overlay_is_open_cached());
+}
+
+#[test]
+fn log_sink() {
+    let mut discord = Discord::<()>::mock();
+    let messages = std::rc::Rc::new(RefCell::new(Vec::new()));
+
+    {
+        let messages = messages.clone();
+        discord.set_log_sink(move |level, message| {
+            messages.borrow_mut().push((level, message.to_string()))
+        });
+    }
+
+    discord.set_log_level(log::LevelFilter::Warn);
+    discord.fire_log(sys::DiscordLogLevel_Warn, "rate limited\0");
+    discord.run_callbacks().unwrap();
+
+    assert_eq!(
+        *messages.borrow(),
+        vec![(log::Level::Warn, "rate limited".to_string())]
+    );
+}
+
+#[test]
+fn fired_events() {
+    #[derive(Default)]
+    struct E {
+        lobby_messages: Vec<(LobbyID, UserID, Vec<u8>)>,
+        lobby_network_messages: Vec<(LobbyID, UserID, NetworkChannelID, Vec<u8>)>,
+        network_messages: Vec<(NetworkPeerID, NetworkChannelID, Vec<u8>)>,
+        refreshes: u32,
+    }
+
+    impl EventHandler for E {
+        fn on_lobby_message(
+            &mut self,
+            _discord: &Discord<'_, Self>,
+            lobby_id: LobbyID,
+            member_id: UserID,
+            data: &[u8],
+        ) {
+            self.lobby_messages
+                .push((lobby_id, member_id, data.to_vec()));
+        }
+
+        fn on_lobby_network_message(
+            &mut self,
+            _discord: &Discord<'_, Self>,
+            lobby_id: LobbyID,
+            member_id: UserID,
+            channel_id: NetworkChannelID,
+            data: &[u8],
+        ) {
+            self.lobby_network_messages
+                .push((lobby_id, member_id, channel_id, data.to_vec()));
+        }
+
+        fn on_network_message(
+            &mut self,
+            _discord: &Discord<'_, Self>,
+            peer_id: NetworkPeerID,
+            channel_id: NetworkChannelID,
+            data: &[u8],
+        ) {
+            self.network_messages
+                .push((peer_id, channel_id, data.to_vec()));
+        }
+
+        fn on_relationships_refresh(&mut self, _discord: &Discord<'_, Self>) {
+            self.refreshes += 1;
+        }
+    }
+
+    let mut discord = Discord::mock();
+    *discord.event_handler_mut() = Some(E::default());
+
+    discord.fire_lobby_message(1, 2, b"hello");
+    discord.fire_lobby_message(1, 2, b"again");
+    discord.fire_lobby_network_message(1, 2, 0, b"state");
+    discord.fire_network_message(3, 4, b"world");
+    discord.fire_relationships_refresh();
+
+    discord.run_callbacks().unwrap();
+
+    let event_handler = discord.event_handler().as_ref().unwrap();
+
+    assert_eq!(
+        event_handler.lobby_messages,
+        vec![(1, 2, b"hello".to_vec()), (1, 2, b"again".to_vec())]
+    );
+    assert_eq!(
+        event_handler.lobby_network_messages,
+        vec![(1, 2, 0, b"state".to_vec())]
+    );
+    assert_eq!(
+        event_handler.network_messages,
+        vec![(3, 4, b"world".to_vec())]
+    );
+    assert_eq!(event_handler.refreshes, 1);
+}
@@ -1,4 +1,9 @@
-use crate::{sys, utils::charbuf_to_str, UnixTimestamp};
+use crate::{
+    sys,
+    utils::{charbuf_to_str, seconds_between, unix_now},
+    UnixTimestamp,
+};
+use std::time::Duration;
 
 /// OAuth 2.0 Token
 ///
@@ -31,6 +36,26 @@ impl OAuth2Token {
     pub fn expires(&self) -> UnixTimestamp {
         self.0.expires
     }
+
+    /// How long until the token expires, zero if it already has
+    ///
+    /// ```rust
+    /// # use discord_game_sdk::*;
+    /// # use std::time::Duration;
+    /// # fn example(token: OAuth2Token) -> Result<()> {
+    /// if token.expires_in() < Duration::from_secs(60) {
+    ///     // request a new token
+    /// }
+    /// # Ok(()) }
+    /// ```
+    pub fn expires_in(&self) -> Duration {
+        seconds_between(unix_now(), self.expires())
+    }
+
+    /// Whether the token has expired
+    pub fn is_expired(&self) -> bool {
+        self.expires() <= unix_now()
+    }
 }
 
 impl std::fmt::Debug for OAuth2Token {
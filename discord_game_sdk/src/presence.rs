@@ -13,9 +13,16 @@ impl Presence {
         self.0.status.into()
     }
 
-    /// The user's current activity
-    pub fn activity(&self) -> &Activity {
-        unsafe { &*(&self.0.activity as *const sys::DiscordActivity as *const Activity) }
+    /// The user's current activity, `None` if the user is not currently in a game
+    pub fn activity(&self) -> Option<&Activity> {
+        let activity =
+            unsafe { &*(&self.0.activity as *const sys::DiscordActivity as *const Activity) };
+
+        if activity.is_empty() {
+            None
+        } else {
+            Some(activity)
+        }
     }
 }
 
@@ -30,6 +37,9 @@ impl std::fmt::Debug for Presence {
 
 impl std::fmt::Display for Presence {
     fn fmt(&self, fmt: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(fmt, "{}, {}", self.status(), self.activity())
+        match self.activity() {
+            Some(activity) => write!(fmt, "{}, {}", self.status(), activity),
+            None => write!(fmt, "{}", self.status()),
+        }
     }
 }
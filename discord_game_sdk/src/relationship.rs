@@ -1,4 +1,4 @@
-use crate::{sys, Presence, RelationshipKind, User};
+use crate::{sys, Presence, RelationshipKind, Status, User};
 
 /// Relationship
 ///
@@ -22,6 +22,38 @@ impl Relationship {
     pub fn presence(&self) -> &Presence {
         unsafe { &*(&self.0.presence as *const sys::DiscordPresence as *const Presence) }
     }
+
+    /// The join secret of the target's current activity,
+    /// `None` if they are offline or their party cannot be joined
+    ///
+    /// A party can be joined if it has a join secret and is not full.
+    ///
+    /// ```rust
+    /// # use discord_game_sdk::*;
+    /// # fn example(discord: Discord<'_, ()>, relationship: Relationship) -> Result<()> {
+    /// if let Some(secret) = relationship.join_secret() {
+    ///     discord.connect_lobby_with_activity_secret(secret, |discord, lobby| {
+    ///         if let Err(error) = lobby {
+    ///             eprintln!("failed to join friend: {}", error);
+    ///         }
+    ///     });
+    /// }
+    /// # Ok(()) }
+    /// ```
+    pub fn join_secret(&self) -> Option<&str> {
+        if self.presence().status() == Status::Offline {
+            return None;
+        }
+
+        let activity = self.presence().activity()?;
+
+        if activity.join_secret().is_empty() || activity.party_amount() >= activity.party_capacity()
+        {
+            return None;
+        }
+
+        Some(activity.join_secret())
+    }
 }
 
 impl std::fmt::Debug for Relationship {
@@ -33,3 +65,38 @@ impl std::fmt::Debug for Relationship {
             .finish()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::write_charbuf;
+
+    fn relationship(status: Status, join_secret: &str, amount: i32) -> Relationship {
+        let mut relationship = Relationship(sys::DiscordRelationship::default());
+        relationship.0.presence.status = status.into();
+        relationship.0.presence.activity.party.size.current_size = amount;
+        relationship.0.presence.activity.party.size.max_size = 4;
+        write_charbuf(
+            &mut relationship.0.presence.activity.secrets.join,
+            join_secret,
+        );
+        relationship
+    }
+
+    #[test]
+    fn test_join_secret() {
+        assert_eq!(
+            relationship(Status::Online, "secret", 1).join_secret(),
+            Some("secret")
+        );
+        assert_eq!(
+            relationship(Status::Offline, "secret", 1).join_secret(),
+            None
+        );
+        assert_eq!(relationship(Status::Online, "", 1).join_secret(), None);
+        assert_eq!(
+            relationship(Status::Online, "secret", 4).join_secret(),
+            None
+        );
+    }
+}
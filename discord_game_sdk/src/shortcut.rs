@@ -0,0 +1,93 @@
+/// Push-to-talk Shortcut
+///
+/// Builds a string in the format expected by
+/// [`InputMode::push_to_talk`](struct.InputMode.html#method.push_to_talk).
+///
+/// > [Shortcut keys in official docs](https://discordapp.com/developers/docs/game-sdk/discord-voice#data-models-shortcut-keys)
+///
+/// ```rust
+/// # use discord_game_sdk::*;
+/// assert_eq!(Shortcut::new().ctrl().key("V").to_string(), "ctrl + v");
+/// assert_eq!(Shortcut::new().key("caps lock").to_string(), "caps lock");
+/// ```
+#[derive(Clone, Debug, Default, Eq, Hash, PartialEq)]
+pub struct Shortcut {
+    ctrl: bool,
+    alt: bool,
+    shift: bool,
+    meta: bool,
+    key: Option<String>,
+}
+
+impl Shortcut {
+    /// Create an empty Shortcut
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Require the `ctrl` key
+    pub fn ctrl(&mut self) -> &mut Self {
+        self.ctrl = true;
+        self
+    }
+
+    /// Require the `alt` key
+    pub fn alt(&mut self) -> &mut Self {
+        self.alt = true;
+        self
+    }
+
+    /// Require the `shift` key
+    pub fn shift(&mut self) -> &mut Self {
+        self.shift = true;
+        self
+    }
+
+    /// Require the `meta` key
+    pub fn meta(&mut self) -> &mut Self {
+        self.meta = true;
+        self
+    }
+
+    /// Set the main key, e.g. `v`, `f1`, `caps lock` or `numpad 0`.
+    ///
+    /// The name is trimmed and lowercased.
+    ///
+    /// `+` separates keys, so it must not be part of the name: `key("shift+v")` is read as the
+    /// two keys `shift` and `v`. If the name is empty or leaves an empty key around a `+`,
+    /// e.g. `+` or `v+`, [`Discord::set_input_mode`](struct.Discord.html#method.set_input_mode)
+    /// fails with [`Error::InvalidPayload`](enum.Error.html#variant.InvalidPayload).
+    pub fn key(&mut self, name: &str) -> &mut Self {
+        let name = name.trim().to_lowercase();
+
+        self.key = Some(name);
+        self
+    }
+}
+
+impl std::fmt::Display for Shortcut {
+    fn fmt(&self, fmt: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let modifiers = [
+            (self.ctrl, "ctrl"),
+            (self.alt, "alt"),
+            (self.shift, "shift"),
+            (self.meta, "meta"),
+        ];
+
+        let keys = modifiers
+            .iter()
+            .filter(|(enabled, _)| *enabled)
+            .map(|(_, name)| *name)
+            .chain(self.key.iter().map(String::as_str));
+
+        for (i, key) in keys.enumerate() {
+            if i > 0 {
+                fmt.write_str(" + ")?;
+            }
+
+            fmt.write_str(key)?;
+        }
+
+        Ok(())
+    }
+}
@@ -1,4 +1,4 @@
-use crate::{sys, utils::charbuf_to_str, SkuKind, Snowflake};
+use crate::{sys, utils::charbuf_to_str, SkuKind, SkuPrice, Snowflake};
 
 /// SKU (stock keeping unit)
 ///
@@ -23,12 +23,17 @@ impl Sku {
         charbuf_to_str(&self.0.name)
     }
 
+    /// How much the SKU costs
+    pub fn price(&self) -> &SkuPrice {
+        unsafe { &*(&self.0.price as *const sys::DiscordSkuPrice as *const SkuPrice) }
+    }
+
     /// The amount of money that the SKU costs
     pub fn price_amount(&self) -> u32 {
         self.0.price.amount
     }
 
-    /// The currency that [`price_amount`](#method.price_currency) is in
+    /// The currency that [`price_amount`](#method.price_amount) is in
     pub fn price_currency(&self) -> &str {
         charbuf_to_str(&self.0.price.currency)
     }
@@ -40,8 +45,7 @@ impl std::fmt::Debug for Sku {
             .field("id", &self.id())
             .field("kind", &self.kind())
             .field("name", &self.name())
-            .field("price_amount", &self.price_amount())
-            .field("price_currency", &self.price_currency())
+            .field("price", &self.price())
             .finish()
     }
 }
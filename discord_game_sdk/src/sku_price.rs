@@ -0,0 +1,29 @@
+use crate::{sys, utils::charbuf_to_str};
+
+/// SKU Price
+///
+/// > [Struct in official docs](https://discordapp.com/developers/docs/game-sdk/store#data-models-skuprice-struct)
+#[derive(Clone, Eq, PartialEq)]
+#[repr(transparent)]
+pub struct SkuPrice(pub(crate) sys::DiscordSkuPrice);
+
+impl SkuPrice {
+    /// The amount of money, in the currency's smallest unit (e.g. cents for `USD`)
+    pub fn amount(&self) -> u32 {
+        self.0.amount
+    }
+
+    /// The ISO 4217 code of the currency, e.g. `USD`
+    pub fn currency(&self) -> &str {
+        charbuf_to_str(&self.0.currency)
+    }
+}
+
+impl std::fmt::Debug for SkuPrice {
+    fn fmt(&self, fmt: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        fmt.debug_struct("SkuPrice")
+            .field("amount", &self.amount())
+            .field("currency", &self.currency())
+            .finish()
+    }
+}
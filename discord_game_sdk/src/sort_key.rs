@@ -0,0 +1,12 @@
+/// File Stat Sorting Key
+///
+/// Used by [`Discord::file_stats_sorted`](struct.Discord.html#method.file_stats_sorted)
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum SortKey {
+    /// Sort by filename, alphabetically
+    Name,
+    /// Sort by size, smallest first
+    Size,
+    /// Sort by last modification time, oldest first
+    Modified,
+}
@@ -29,6 +29,18 @@ impl From<sys::EDiscordStatus> for Status {
     }
 }
 
+impl Into<sys::EDiscordStatus> for Status {
+    fn into(self) -> sys::EDiscordStatus {
+        match self {
+            Self::DoNotDisturb => sys::DiscordStatus_DoNotDisturb,
+            Self::Idle => sys::DiscordStatus_Idle,
+            Self::Offline => sys::DiscordStatus_Offline,
+            Self::Online => sys::DiscordStatus_Online,
+            Self::Undefined(n) => n,
+        }
+    }
+}
+
 impl std::fmt::Display for Status {
     fn fmt(&self, fmt: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(
@@ -44,3 +56,23 @@ impl std::fmt::Display for Status {
         )
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_status_round_trip() {
+        for &status in &[
+            Status::DoNotDisturb,
+            Status::Idle,
+            Status::Offline,
+            Status::Online,
+            Status::Undefined(42),
+        ] {
+            let raw: sys::EDiscordStatus = status.into();
+
+            assert_eq!(Status::from(raw), status);
+        }
+    }
+}
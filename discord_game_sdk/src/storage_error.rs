@@ -0,0 +1,85 @@
+use crate::Error;
+use std::{fmt, io, string::FromUtf8Error};
+
+/// Error of the storage methods that check the data they read, so that bad data can be
+/// told apart from an [`Error`] reported by the SDK, such as an invalid key
+///
+/// Converts to `io::Error`, with `io::ErrorKind::InvalidData` for bad data.
+///
+/// [`Error`]: enum.Error.html
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum StorageError {
+    /// Error reported by the SDK, or an invalid key
+    Sdk(Error),
+
+    /// The data does not start with the header written by
+    /// [`Discord::write_file_compressed`](struct.Discord.html#method.write_file_compressed),
+    /// or the header has another version
+    NotCompressed,
+
+    /// The data has a valid header, but fails to decompress
+    Corrupted,
+
+    /// The file is larger than the maximum allowed by the caller
+    TooLarge {
+        /// Size of the file, in bytes
+        size: u64,
+        /// Maximum size, in bytes
+        max: u64,
+    },
+
+    /// The offset is past the end of the file
+    OffsetOutOfRange {
+        /// Requested offset, in bytes
+        offset: u64,
+        /// Size of the file, in bytes
+        size: u64,
+    },
+
+    /// The data is not valid UTF-8, the error holds the bytes that were read
+    InvalidUtf8(FromUtf8Error),
+}
+
+impl From<Error> for StorageError {
+    fn from(error: Error) -> Self {
+        StorageError::Sdk(error)
+    }
+}
+
+impl fmt::Display for StorageError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            StorageError::Sdk(error) => write!(f, "{}", error),
+            StorageError::NotCompressed => write!(f, "data is not compressed"),
+            StorageError::Corrupted => write!(f, "compressed data is corrupted"),
+            StorageError::TooLarge { size, max } => {
+                write!(f, "file is {} bytes, larger than {} bytes", size, max)
+            }
+            StorageError::OffsetOutOfRange { offset, size } => write!(
+                f,
+                "offset {} is past the end of the file of {} bytes",
+                offset, size
+            ),
+            StorageError::InvalidUtf8(error) => write!(f, "data is not valid UTF-8: {}", error),
+        }
+    }
+}
+
+impl std::error::Error for StorageError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            StorageError::Sdk(error) => Some(error),
+            StorageError::InvalidUtf8(error) => Some(error),
+            _ => None,
+        }
+    }
+}
+
+impl From<StorageError> for io::Error {
+    fn from(error: StorageError) -> Self {
+        match error {
+            StorageError::Sdk(error) => error.into(),
+            error => io::Error::new(io::ErrorKind::InvalidData, error),
+        }
+    }
+}
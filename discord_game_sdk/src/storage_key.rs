@@ -0,0 +1,106 @@
+use crate::{sys, Error, Result};
+use std::{borrow::Cow, convert::TryFrom, fmt};
+
+/// Storage key, validated once and stored with a trailing nul byte
+///
+/// Storage methods that take a single key accept `&StorageKey` as well as strings.
+/// Invalid keys are rejected when the `StorageKey` is created rather than by each call,
+/// and the key is never copied to append a nul byte.
+///
+/// ```rust
+/// # use discord_game_sdk::*;
+/// # fn example(discord: Discord<'_, ()>) -> Result<()> {
+/// let key = StorageKey::new("profile_1.save")?;
+///
+/// discord.write_file(&key, b"important save data")?;
+/// let contents = discord.read_file_to_vec_capped(&key, 1024);
+/// # Ok(()) }
+/// ```
+#[derive(Clone, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct StorageKey(String);
+
+impl StorageKey {
+    /// Validates a key, a trailing nul byte is allowed.
+    ///
+    /// ## Errors
+    ///
+    /// Returns [`Error::InvalidPayload`](enum.Error.html#variant.InvalidPayload) if `key` is
+    /// 260 bytes or longer, or contains a nul byte that is not trailing.
+    pub fn new(key: &str) -> Result<Self> {
+        check_filename(key)?;
+
+        let mut key = key.trim_end_matches('\0').to_string();
+        key.push('\0');
+
+        Ok(StorageKey(key))
+    }
+
+    /// The key, without its trailing nul byte
+    pub fn as_str(&self) -> &str {
+        &self.0[..self.0.len() - 1]
+    }
+}
+
+impl TryFrom<&str> for StorageKey {
+    type Error = Error;
+
+    fn try_from(key: &str) -> Result<Self> {
+        Self::new(key)
+    }
+}
+
+impl<'s> From<&'s StorageKey> for Cow<'s, str> {
+    fn from(key: &'s StorageKey) -> Self {
+        Cow::Borrowed(&key.0)
+    }
+}
+
+impl fmt::Display for StorageKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl fmt::Debug for StorageKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("StorageKey").field(&self.as_str()).finish()
+    }
+}
+
+// The SDK truncates keys at the first nul byte and to the size of `DiscordFileStat::filename`
+pub(crate) fn check_filename(filename: &str) -> Result<()> {
+    let filename = filename.trim_end_matches('\0');
+
+    let capacity = sys::DiscordFileStat::default().filename.len();
+
+    if filename.contains('\0') || filename.len() >= capacity {
+        return Err(Error::InvalidPayload);
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::convert::TryInto;
+
+    #[test]
+    fn validation() {
+        let key = StorageKey::new("slot1/save").unwrap();
+
+        assert_eq!(key.as_str(), "slot1/save");
+        assert_eq!(Cow::from(&key), "slot1/save\0");
+        assert_eq!(StorageKey::new("slot1/save\0").unwrap(), key);
+        assert_eq!("slot1/save".try_into(), Ok(key));
+
+        assert_eq!(
+            StorageKey::new("slot1/save\0.bak"),
+            Err(Error::InvalidPayload)
+        );
+        assert_eq!(
+            StorageKey::new(&"a".repeat(260)),
+            Err(Error::InvalidPayload)
+        );
+    }
+}
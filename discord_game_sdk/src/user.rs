@@ -23,6 +23,26 @@ impl User {
         charbuf_to_str(&self.0.discriminator)
     }
 
+    /// The discriminator of users who have not migrated to unique usernames
+    ///
+    /// Migrated users have a discriminator of `0`, for which this returns `None`.
+    ///
+    /// ```rust
+    /// # use discord_game_sdk::*;
+    /// # fn example(user: User) {
+    /// let name = match user.legacy_discriminator() {
+    ///     Some(discriminator) => format!("{}#{}", user.username(), discriminator),
+    ///     None => user.username().to_string(),
+    /// };
+    /// # }
+    /// ```
+    pub fn legacy_discriminator(&self) -> Option<&str> {
+        match self.discriminator() {
+            "" | "0" => None,
+            discriminator => Some(discriminator),
+        }
+    }
+
     /// The hash of the user's avatar
     pub fn avatar(&self) -> &str {
         charbuf_to_str(&self.0.avatar)
@@ -33,6 +53,32 @@ impl User {
         self.0.bot
     }
 
+    /// Index of the default avatar Discord displays when [`avatar`](#method.avatar) is empty,
+    /// as found at `https://cdn.discordapp.com/embed/avatars/{index}.png`
+    ///
+    /// Users with a legacy discriminator use `discriminator % 5`,
+    /// users who migrated to unique usernames (discriminator `0`) use `(id >> 22) % 6`.
+    pub fn default_avatar_index(&self) -> u8 {
+        match self.discriminator().parse::<u16>() {
+            Ok(discriminator) if discriminator != 0 => (discriminator % 5) as u8,
+            _ => ((self.id() as u64 >> 22) % 6) as u8,
+        }
+    }
+
+    /// A color derived from the user's ID, e.g. to tint their nameplate
+    ///
+    /// This is not a color chosen by the user, it is the same for a given user across sessions
+    /// and versions of this crate.
+    pub fn accent_color_fallback(&self) -> [u8; 3] {
+        // SplitMix64 finalizer, spreads sequential snowflakes across the color space
+        let mut hash = self.id() as u64;
+        hash = (hash ^ (hash >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        hash = (hash ^ (hash >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        hash ^= hash >> 31;
+
+        [(hash >> 16) as u8, (hash >> 8) as u8, hash as u8]
+    }
+
     /// Create an [Image Handle](struct.ImageHandle.html) targeting the user's avatar
     pub fn image_handle(&self, size: u32) -> ImageHandle {
         ImageHandle::from_user_id(self.id(), size)
@@ -50,3 +96,65 @@ impl std::fmt::Debug for User {
             .finish()
     }
 }
+
+impl std::fmt::Display for User {
+    fn fmt(&self, fmt: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.legacy_discriminator() {
+            Some(discriminator) => write!(fmt, "{}#{}", self.username(), discriminator),
+            None => write!(fmt, "{}", self.username()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::write_charbuf;
+
+    fn user(id: UserID, discriminator: &str) -> User {
+        let mut user = User(sys::DiscordUser::default());
+        user.0.id = id;
+        write_charbuf(&mut user.0.discriminator, discriminator);
+        user
+    }
+
+    #[test]
+    fn test_legacy_discriminator() {
+        let mut legacy = user(0, "0047");
+        write_charbuf(&mut legacy.0.username, "twiikuu");
+
+        assert_eq!(legacy.legacy_discriminator(), Some("0047"));
+        assert_eq!(legacy.to_string(), "twiikuu#0047");
+
+        let mut migrated = user(0, "0");
+        write_charbuf(&mut migrated.0.username, "ldesgoui");
+
+        assert_eq!(migrated.legacy_discriminator(), None);
+        assert_eq!(migrated.to_string(), "ldesgoui");
+    }
+
+    #[test]
+    fn test_accent_color_fallback() {
+        let color = user(80_351_110_224_678_912, "0").accent_color_fallback();
+
+        assert_eq!(color, [148, 161, 243]);
+
+        assert_eq!(
+            color,
+            user(80_351_110_224_678_912, "1337").accent_color_fallback()
+        );
+        assert_ne!(
+            color,
+            user(80_351_110_224_678_913, "0").accent_color_fallback()
+        );
+    }
+
+    #[test]
+    fn test_default_avatar_index() {
+        assert_eq!(user(0, "0047").default_avatar_index(), 2);
+        assert_eq!(user(0, "1337").default_avatar_index(), 2);
+        assert_eq!(user(0, "0005").default_avatar_index(), 0);
+        assert_eq!(user(3 << 22, "0").default_avatar_index(), 3);
+        assert_eq!(user(80_351_110_224_678_912, "0").default_avatar_index(), 5);
+    }
+}
@@ -11,10 +11,7 @@ pub(crate) fn abort_on_panic<R>(callback: impl FnOnce() -> R + std::panic::Unwin
     match std::panic::catch_unwind(callback) {
         Ok(r) => r,
         Err(e) => {
-            if let Some(info) = e.downcast_ref::<&str>() {
-                log::error!("panic across FFI bounds: {}", info);
-                eprintln!("\n{}\n\n{}\n", ACROSS_FFI, info);
-            } else if let Some(info) = e.downcast_ref::<String>() {
+            if let Some(info) = panic_message(&*e) {
                 log::error!("panic across FFI bounds: {}", info);
                 eprintln!("\n{}\n\n{}\n", ACROSS_FFI, info);
             } else {
@@ -27,6 +24,72 @@ pub(crate) fn abort_on_panic<R>(callback: impl FnOnce() -> R + std::panic::Unwin
     }
 }
 
+// Unlike `EventHandler` methods, callbacks are consumed when called,
+// no state is left behind if they panic and it is safe to resume
+pub(crate) fn log_on_panic(callback: impl FnOnce() + std::panic::UnwindSafe) {
+    if let Err(e) = std::panic::catch_unwind(callback) {
+        if let Some(info) = panic_message(&*e) {
+            log::error!("panic in callback: {}", info);
+        } else {
+            log::error!("panic in callback");
+        }
+    }
+}
+
+fn panic_message(payload: &(dyn std::any::Any + Send)) -> Option<&str> {
+    payload
+        .downcast_ref::<&str>()
+        .copied()
+        .or_else(|| payload.downcast_ref::<String>().map(String::as_str))
+}
+
+thread_local! {
+    static NUL_TERMINATED: std::cell::RefCell<String> = std::cell::RefCell::new(String::new());
+}
+
+// Avoids allocating a nul-terminated copy of `value` for every synchronous call,
+// the pointer must not be kept after `callback` returns
+pub(crate) fn with_nul_terminated<R>(value: &str, callback: impl FnOnce(*const u8) -> R) -> R {
+    if value.ends_with('\0') {
+        return callback(value.as_ptr());
+    }
+
+    NUL_TERMINATED.with(|buffer| match buffer.try_borrow_mut() {
+        Ok(mut buffer) => {
+            buffer.clear();
+            buffer.push_str(value);
+            buffer.push('\0');
+
+            callback(buffer.as_ptr())
+        }
+        // Reentrant call, e.g. from an `EventHandler` method
+        Err(_) => callback(format!("{}\0", value).as_ptr()),
+    })
+}
+
+pub(crate) fn unix_now() -> crate::UnixTimestamp {
+    use std::convert::TryInto;
+
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|now| {
+            now.as_secs()
+                .try_into()
+                .unwrap_or(crate::UnixTimestamp::max_value())
+        })
+        .unwrap_or(0)
+}
+
+/// Saturates to zero if `to` is before `from`
+pub(crate) fn seconds_between(
+    from: crate::UnixTimestamp,
+    to: crate::UnixTimestamp,
+) -> std::time::Duration {
+    use std::convert::TryInto;
+
+    std::time::Duration::from_secs(to.saturating_sub(from).try_into().unwrap_or(0))
+}
+
 pub(crate) fn charbuf_to_str(charbuf: &[u8]) -> &str {
     let bytes = &charbuf[..charbuf_len(charbuf)];
 
@@ -64,9 +127,18 @@ pub(crate) unsafe fn charptr_to_str<'a>(ptr: *const u8) -> &'a str {
     }
 }
 
+/// Reinterprets a pointer to an SDK struct as a reference to its `#[repr(transparent)]` wrapper
+pub(crate) unsafe fn ptr_to_ref<'a, S, T>(ptr: *const S) -> &'a T {
+    debug_assert!(!ptr.is_null());
+    debug_assert_eq!(std::mem::size_of::<S>(), std::mem::size_of::<T>());
+
+    &*(ptr as *const T)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::{sys, User};
 
     #[test]
     fn test_write_charbuf() {
@@ -82,6 +154,42 @@ mod tests {
         run_test("65 characters 65 characters 65 characters 65 characters 65 charac");
     }
 
+    #[test]
+    fn test_ptr_to_ref() {
+        let user = sys::DiscordUser {
+            id: 42,
+            ..Default::default()
+        };
+
+        let user: &User = unsafe { ptr_to_ref(&user) };
+
+        assert_eq!(user.id(), 42);
+    }
+
+    #[test]
+    #[should_panic]
+    #[cfg(debug_assertions)]
+    fn panic_test_ptr_to_ref() {
+        let _: &User = unsafe { ptr_to_ref(std::ptr::null::<sys::DiscordUser>()) };
+    }
+
+    #[test]
+    fn test_with_nul_terminated() {
+        for &value in &["", "profile_1.save", "profile_1.save\0"] {
+            let read = with_nul_terminated(value, |ptr| unsafe { charptr_to_str(ptr) }.to_string());
+
+            assert_eq!(read, value.trim_end_matches('\0'));
+        }
+
+        let nested = with_nul_terminated("outer", |outer| {
+            with_nul_terminated("inner", |inner| unsafe {
+                format!("{} {}", charptr_to_str(outer), charptr_to_str(inner))
+            })
+        });
+
+        assert_eq!(nested, "outer inner");
+    }
+
     fn run_test(val: &str) {
         let mut charbuf = [0u8; 64];
 